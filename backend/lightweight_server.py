@@ -38,6 +38,9 @@ from scripts import clip_extraction
 logging.basicConfig(level=logging.INFO)
 logger = logging.getLogger(__name__)
 
+# Kept in sync with the app version by scripts/bump-version.js
+SERVER_VERSION = "0.0.13"
+
 # GitHub repository for ML environment releases (owner/repo)
 GITHUB_REPO = "sammlapp/dipper"
 
@@ -53,6 +56,35 @@ PYTORCH_ENV_ASSET = {
     "Linux": {"source": "hf", "name": "dipper_pytorch_env-linux-x64.tar.gz"},
 }
 
+# Features this server supports, reported by /health and /capabilities
+CAPABILITIES = [
+    "scan_folder",
+    "get_sample_detections",
+    "load_scores",
+    "load_extraction_task",
+    "config_management",
+    "env_management",
+    "inference_runner",
+    "training_runner",
+    "extraction_runner",
+]
+
+# model_source values the inference runner accepts (see scripts/load_model.py)
+MODEL_SOURCES = ["bmz", "local_file", "custom", "mlp_classifier", "ribbit", "cwt_detector"]
+
+
+def gpu_available():
+    """Whether inference can use a GPU: an NVIDIA card with a working driver, or Apple Silicon"""
+    if platform.system() == "Darwin" and platform.machine() == "arm64":
+        return True
+    try:
+        result = subprocess.run(
+            ["nvidia-smi", "--list-gpus"], capture_output=True, text=True, timeout=5
+        )
+        return result.returncode == 0 and bool(result.stdout.strip())
+    except (OSError, subprocess.TimeoutExpired):
+        return False
+
 
 def is_process_alive(pid):
     """
@@ -1384,6 +1416,8 @@ class LightweightServer:
         """Setup HTTP routes"""
         self.app.router.add_get("/", self.root_handler)
         self.app.router.add_get("/health", self.health_check)
+        self.app.router.add_get("/capabilities", self.capabilities)
+        self.app.router.add_post("/models/info", self.model_info)
         self.app.router.add_post("/scan_folder", self.scan_folder)
         self.app.router.add_post("/get_sample_detections", self.get_sample_detections)
         self.app.router.add_post("/load_scores", self.load_scores)
@@ -1462,20 +1496,69 @@ class LightweightServer:
                 "message": f"Lightweight server running on port {self.port}",
                 "port": self.port,
                 "server_type": "lightweight",
-                "capabilities": [
-                    "scan_folder",
-                    "get_sample_detections",
-                    "load_scores",
-                    "load_extraction_task",
-                    "config_management",
-                    "env_management",
-                    "inference_runner",
-                    "training_runner",
-                    "extraction_runner",
-                ],
+                "version": SERVER_VERSION,
+                "capabilities": CAPABILITIES,
+            }
+        )
+
+    async def capabilities(self, request):
+        """Feature manifest the app uses to show or hide UI features"""
+        gpu = await asyncio.get_event_loop().run_in_executor(None, gpu_available)
+        return web.json_response(
+            {
+                "gpu": gpu,
+                "sse_progress": False,
+                "model_types": MODEL_SOURCES,
+                "features": CAPABILITIES,
             }
         )
 
+    async def model_info(self, request):
+        """Report the sample rate and channel count a local model file expects.
+        The model is loaded in the inference environment, since this server has no torch.
+        """
+        try:
+            data = await request.json()
+            model_path = data.get("model_path")
+            if not model_path:
+                return web.json_response({"error": "model_path required"}, status=400)
+            model_path = resolve_path(model_path)
+            if not os.path.isfile(model_path):
+                return web.json_response(
+                    {"error": f"Model file not found: {model_path}"}, status=404
+                )
+
+            env_result = setup_environment(data.get("env_path"))
+            if env_result["status"] != "ready":
+                return web.json_response(env_result, status=500)
+
+            script = os.path.join(os.path.dirname(__file__), "scripts", "model_info.py")
+            proc = await asyncio.get_event_loop().run_in_executor(
+                None,
+                lambda: subprocess.run(
+                    [env_result["python_path"], script, "--model", model_path],
+                    capture_output=True,
+                    text=True,
+                    timeout=300,
+                    cwd=os.path.dirname(os.path.abspath(__file__)),
+                ),
+            )
+            if proc.returncode != 0:
+                stderr = proc.stderr[-2000:] if proc.stderr else ""
+                return web.json_response(
+                    {"error": f"Failed to load model:\n{stderr}"}, status=500
+                )
+            # Take last line (avoid any stray print output before ours)
+            lines = [l for l in proc.stdout.splitlines() if l.strip()]
+            if not lines:
+                return web.json_response(
+                    {"error": "Model info script printed nothing"}, status=500
+                )
+            return web.json_response(json.loads(lines[-1]))
+        except Exception as e:
+            logger.error(f"Error reading model info: {e}")
+            return web.json_response({"error": str(e)}, status=500)
+
     async def get_temp_dir(self, request):
         """Return the system temporary directory path"""
         try:
@@ -3391,7 +3474,20 @@ def main():
         default=None,
         help="Parent process PID for heartbeat monitoring",
     )
+    parser.add_argument(
+        "--model-dir",
+        type=str,
+        default=None,
+        help="Directory for cached models (exported to subprocesses as DIPPER_MODEL_DIR)",
+    )
+    parser.add_argument(
+        "--inference-batch-size",
+        type=int,
+        default=None,
+        help="Default inference batch size for configs that don't set one (exported as DIPPER_INFERENCE_BATCH_SIZE)",
+    )
     parser.add_argument("--test", action="store_true", help="Run quick test and exit")
+    parser.add_argument("--version", action="version", version=SERVER_VERSION)
 
     args = parser.parse_args()
 
@@ -3421,6 +3517,14 @@ def main():
 
     logger.info(f"Server will start on {host}:{port}")
 
+    if args.model_dir:
+        os.environ["DIPPER_MODEL_DIR"] = args.model_dir
+        logger.info(f"Using model directory: {args.model_dir}")
+
+    if args.inference_batch_size:
+        os.environ["DIPPER_INFERENCE_BATCH_SIZE"] = str(args.inference_batch_size)
+        logger.info(f"Default inference batch size: {args.inference_batch_size}")
+
     if args.test:
         print("[OK] Lightweight server test successful!")
         print(f"Python version: {sys.version}")
@@ -514,6 +514,18 @@ def classify_from_hoplite_embeddings(files, classifier, config_data):
     return pd.DataFrame(preds, index=clips.index, columns=classifier.class_names)
 
 
+def apply_default_batch_size(config_data):
+    """Fill in the batch size chosen in Dipper's settings where the config has none"""
+    default = os.environ.get("DIPPER_INFERENCE_BATCH_SIZE")
+    if not default:
+        return
+    for key in ("inference_settings", "inference_config"):
+        settings = config_data.get(key)
+        if isinstance(settings, dict) and "batch_size" not in settings:
+            settings["batch_size"] = int(default)
+            logger.info(f"Using default batch size {default} for {key}")
+
+
 def main():
     parser = argparse.ArgumentParser(description="Run bioacoustics model inference")
     parser.add_argument(
@@ -523,6 +535,7 @@ def main():
 
     # Load configuration from file
     config_data = load_config_file(args.config, logger=logger)
+    apply_default_batch_size(config_data)
 
     # Get job folder for status updates
     job_folder = Path(config_data["job_folder"])
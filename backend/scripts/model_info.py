@@ -0,0 +1,32 @@
+#!/usr/bin/env python3
+"""
+Report the audio input a saved OpenSoundscape model expects
+Prints one JSON line: {"sample_rate": <int or null>, "channels": 1}
+"""
+
+import argparse
+import json
+
+import torch
+
+
+def model_info(model_path):
+    """Load a model file and read its preprocessing sample rate"""
+    model = torch.load(model_path, weights_only=False, map_location="cpu")
+    sample_rate = None
+    try:
+        sample_rate = model.preprocessor.pipeline.load_audio.params["sample_rate"]
+    except (AttributeError, KeyError, TypeError):
+        pass
+    # OpenSoundscape loads audio as mono; None means the file's own rate is kept
+    return {
+        "sample_rate": int(sample_rate) if sample_rate else None,
+        "channels": 1,
+    }
+
+
+if __name__ == "__main__":
+    parser = argparse.ArgumentParser(description="Report a model's expected audio input")
+    parser.add_argument("--model", required=True, help="Path to the saved model file")
+    args = parser.parse_args()
+    print(json.dumps(model_info(args.model)))
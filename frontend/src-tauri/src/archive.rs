@@ -0,0 +1,132 @@
+//! Archiving output folders: a streaming `.tar.gz` writer (GNU tar headers, so long
+//! names and files over 8 GB are stored without truncation) and the file counts
+//! used to verify an archive or copy before the original is removed
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use tar::{Archive, Builder, Header};
+
+/// Number of regular files under `dir`. Symlinks are an error: they can't be
+/// archived or copied faithfully, and removing the original would lose them.
+pub fn count_files(dir: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let meta = fs::symlink_metadata(&path)?;
+        if meta.file_type().is_symlink() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("symlinks can't be archived: {}", path.display()),
+            ));
+        }
+        if meta.is_dir() {
+            count += count_files(&path)?;
+        } else if meta.is_file() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Copy the tree under `from` into the new folder `to`, returning the files copied.
+/// `to` must not exist yet; a partial copy is removed if the copy fails.
+pub fn copy_tree(from: &Path, to: &Path) -> io::Result<usize> {
+    fs::create_dir(to)?;
+    copy_contents(from, to).inspect_err(|_| {
+        let _ = fs::remove_dir_all(to);
+    })
+}
+
+fn copy_contents(from: &Path, to: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let meta = fs::symlink_metadata(entry.path())?;
+        let target = to.join(entry.file_name());
+        if meta.is_dir() {
+            fs::create_dir(&target)?;
+            count += copy_contents(&entry.path(), &target)?;
+        } else if meta.is_file() {
+            fs::copy(entry.path(), &target)?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Write `dir` as a gzipped tar at `archive`, with entries under the folder's own
+/// name. The archive must not exist yet, and is removed again if writing fails. File
+/// contents are streamed, so memory use doesn't grow with their size. Returns the
+/// number of files written.
+pub fn write_tar_gz(dir: &Path, archive: &Path) -> io::Result<usize> {
+    let name = dir.file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "folder has no name"))?;
+    let file = fs::OpenOptions::new().write(true).create_new(true).open(archive)?;
+    write_archive(file, dir, Path::new(name)).inspect_err(|_| {
+        let _ = fs::remove_file(archive);
+    })
+}
+
+fn write_archive(file: fs::File, dir: &Path, name: &Path) -> io::Result<usize> {
+    let mut builder = Builder::new(GzEncoder::new(BufWriter::new(file), Compression::default()));
+    builder.follow_symlinks(false);
+
+    builder.append_dir(name, dir)?;
+    let count = write_dir(&mut builder, dir, name)?;
+    builder.into_inner()?
+        .finish()?
+        .into_inner()
+        .map_err(|e| e.into_error())?
+        .sync_all()?;
+    Ok(count)
+}
+
+fn write_dir(builder: &mut Builder<impl Write>, dir: &Path, prefix: &Path) -> io::Result<usize> {
+    let mut entries: Vec<_> = fs::read_dir(dir)?.collect::<io::Result<_>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let mut count = 0;
+    for entry in entries {
+        let path = entry.path();
+        let meta = fs::symlink_metadata(&path)?;
+        let name = prefix.join(entry.file_name());
+        if meta.is_dir() {
+            builder.append_dir(&name, &path)?;
+            count += write_dir(builder, &path, &name)?;
+        } else if meta.is_file() {
+            // The header records the size up front, so a file that changes while
+            // being read would leave the rest of the archive misaligned
+            let mut header = Header::new_gnu();
+            header.set_metadata(&meta);
+            let mut contents = fs::File::open(&path)?.take(meta.len());
+            builder.append_data(&mut header, &name, &mut contents)?;
+            if contents.limit() != 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("{} changed size while being archived", path.display()),
+                ));
+            }
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Read an archive written by `write_tar_gz` back to the end, checking the gzip
+/// stream is intact, and return the number of regular files in it
+pub fn count_tar_gz_files(archive: &Path) -> io::Result<usize> {
+    let mut archive = Archive::new(GzDecoder::new(BufReader::new(fs::File::open(archive)?)));
+    let mut count = 0;
+    for entry in archive.entries()? {
+        if entry?.header().entry_type().is_file() {
+            count += 1;
+        }
+    }
+    // Reading to the end verifies the gzip checksum
+    io::copy(&mut archive.into_inner(), &mut io::sink())?;
+    Ok(count)
+}
@@ -0,0 +1,403 @@
+//! Lightweight audio header inspection shared by the file commands
+
+use rubato::Resampler;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom};
+use std::path::Path;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{Decoder, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::{Hint, ProbeResult};
+
+/// Extensions offered in the "Audio Files" picker filter and used for folder scans
+pub const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "flac", "ogg", "m4a"];
+
+/// Basic stream properties read from an audio file's headers
+pub struct AudioProps {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Unknown for streams whose headers don't report a frame count
+    pub duration_seconds: Option<f64>,
+    /// Codec short name as reported by symphonia, e.g. "pcm_s16le", "mp3", "flac"
+    pub format: String,
+}
+
+fn open_format(path: &Path) -> Result<ProbeResult, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let ext = path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mut hint = Hint::new();
+    if !ext.is_empty() {
+        hint.with_extension(&ext);
+    }
+
+    symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Unrecognized audio format: {}", e))
+}
+
+/// Probe an audio file's container headers without decoding any samples
+pub fn probe(path: &Path) -> Result<AudioProps, String> {
+    let probed = open_format(path)?;
+    let track = probed.format.default_track()
+        .ok_or_else(|| "No audio track found".to_string())?;
+    let params = &track.codec_params;
+
+    let sample_rate = params.sample_rate
+        .ok_or_else(|| "Sample rate not reported in headers".to_string())?;
+    let channels = params.channels.map(|c| c.count() as u16).unwrap_or(0);
+    let duration_seconds = params.n_frames.map(|frames| frames as f64 / sample_rate as f64);
+    let format = symphonia::default::get_codecs()
+        .get_codec(params.codec)
+        .map(|codec| codec.short_name.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Ok(AudioProps { sample_rate, channels, duration_seconds, format })
+}
+
+// GUANO is a few hundred bytes of text; anything far beyond that is a corrupt header
+const MAX_GUANO_CHUNK_BYTES: u64 = 1 << 20;
+
+/// Read GUANO metadata (the `guan` RIFF chunk) from a WAV file.
+/// Returns `None` when the file has no GUANO chunk.
+pub fn read_guano(path: &Path) -> Result<Option<BTreeMap<String, String>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut reader = BufReader::new(file);
+
+    let mut riff_header = [0u8; 12];
+    reader.read_exact(&mut riff_header)
+        .map_err(|_| "File is too short to be a WAV file".to_string())?;
+    if &riff_header[0..4] != b"RIFF" || &riff_header[8..12] != b"WAVE" {
+        return Err("Not a RIFF/WAVE file".to_string());
+    }
+
+    let mut chunk_header = [0u8; 8];
+    while reader.read_exact(&mut chunk_header).is_ok() {
+        let size = u32::from_le_bytes([chunk_header[4], chunk_header[5], chunk_header[6], chunk_header[7]]) as u64;
+        if &chunk_header[0..4] == b"guan" {
+            // The size comes from the file; never allocate more than it actually holds
+            if size > MAX_GUANO_CHUNK_BYTES {
+                return Err(format!("GUANO chunk is implausibly large ({} bytes)", size));
+            }
+            let mut data = Vec::new();
+            (&mut reader).take(size).read_to_end(&mut data)
+                .map_err(|e| format!("Failed to read GUANO chunk: {}", e))?;
+            if data.len() as u64 != size {
+                return Err("GUANO chunk is truncated".to_string());
+            }
+            return Ok(Some(parse_guano(&String::from_utf8_lossy(&data))));
+        }
+        // Chunks are padded to an even number of bytes
+        let skip = size + (size % 2);
+        reader.seek(SeekFrom::Current(skip as i64))
+            .map_err(|e| format!("Failed to read WAV chunks: {}", e))?;
+    }
+    Ok(None)
+}
+
+/// Parse GUANO `Key: Value` lines
+fn parse_guano(text: &str) -> BTreeMap<String, String> {
+    text.lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            let key = key.trim();
+            (!key.is_empty()).then(|| (key.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Latitude/longitude from a GUANO `Loc Position` field ("lat lon")
+pub fn guano_position(guano: &BTreeMap<String, String>) -> Option<(f64, f64)> {
+    let mut parts = guano.get("Loc Position")?.split_whitespace();
+    let lat = parts.next()?.parse().ok()?;
+    let lon = parts.next()?.parse().ok()?;
+    Some((lat, lon))
+}
+
+/// Encoding for converted audio
+#[derive(Clone, Deserialize)]
+pub struct AudioTarget {
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// 16 or 24 for integer PCM, 32 for float
+    pub bit_depth: u16,
+}
+
+impl AudioTarget {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.sample_rate == 0 || self.channels == 0 {
+            return Err("Target sample rate and channels must be non-zero".to_string());
+        }
+        if ![16, 24, 32].contains(&self.bit_depth) {
+            return Err(format!("Unsupported bit depth {} (use 16, 24, or 32)", self.bit_depth));
+        }
+        Ok(())
+    }
+}
+
+/// Packet-by-packet decoder yielding interleaved f32 samples
+pub struct DecodedStream {
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn Decoder>,
+    track_id: u32,
+    pub sample_rate: u32,
+    pub channels: usize,
+    pub bits_per_sample: Option<u32>,
+}
+
+impl DecodedStream {
+    pub fn open(path: &Path) -> Result<DecodedStream, String> {
+        let format = open_format(path)?.format;
+        let track = format.default_track()
+            .ok_or_else(|| "No audio track found".to_string())?;
+        let sample_rate = track.codec_params.sample_rate
+            .ok_or_else(|| "Sample rate not reported in headers".to_string())?;
+        let channels = track.codec_params.channels
+            .map(|c| c.count())
+            .ok_or_else(|| "Channel layout not reported in headers".to_string())?;
+        let decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|e| format!("Unsupported codec: {}", e))?;
+        let track_id = track.id;
+        let bits_per_sample = track.codec_params.bits_per_sample;
+        Ok(DecodedStream { format, decoder, track_id, sample_rate, channels, bits_per_sample })
+    }
+
+    /// Next chunk of interleaved samples, or `None` at end of stream
+    pub fn next_chunk(&mut self) -> Result<Option<Vec<f32>>, String> {
+        loop {
+            let packet = match self.format.next_packet() {
+                Ok(packet) => packet,
+                Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(SymphoniaError::ResetRequired) => return Ok(None),
+                Err(e) => return Err(format!("Failed to read audio: {}", e)),
+            };
+            if packet.track_id() != self.track_id {
+                continue;
+            }
+            match self.decoder.decode(&packet) {
+                Ok(decoded) => {
+                    let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+                    buffer.copy_interleaved_ref(decoded);
+                    return Ok(Some(buffer.samples().to_vec()));
+                }
+                // Skip corrupt packets rather than failing the whole file
+                Err(SymphoniaError::DecodeError(_)) => continue,
+                Err(e) => return Err(format!("Failed to decode audio: {}", e)),
+            }
+        }
+    }
+}
+
+/// Map interleaved frames to `out_channels` planar channels: average to mono,
+/// repeat channels when upmixing, otherwise keep the first channels
+fn remix(interleaved: &[f32], in_channels: usize, out_channels: usize, planar: &mut [Vec<f32>]) {
+    for frame in interleaved.chunks_exact(in_channels) {
+        if out_channels == 1 {
+            planar[0].push(frame.iter().sum::<f32>() / in_channels as f32);
+        } else {
+            for (c, channel) in planar.iter_mut().enumerate() {
+                channel.push(frame[c % in_channels]);
+            }
+        }
+    }
+}
+
+/// WAV writer for planar f32 frames at a chosen bit depth
+pub struct WavSink {
+    writer: hound::WavWriter<BufWriter<File>>,
+    bit_depth: u16,
+}
+
+impl WavSink {
+    pub fn create(path: &Path, sample_rate: u32, channels: u16, bit_depth: u16) -> Result<WavSink, String> {
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: bit_depth,
+            sample_format: if bit_depth == 32 { hound::SampleFormat::Float } else { hound::SampleFormat::Int },
+        };
+        let writer = hound::WavWriter::create(path, spec)
+            .map_err(|e| format!("Failed to create WAV file: {}", e))?;
+        Ok(WavSink { writer, bit_depth })
+    }
+
+    pub fn write_planar(&mut self, planar: &[Vec<f32>], frames: usize) -> Result<(), String> {
+        for i in 0..frames {
+            for channel in planar {
+                let sample = channel[i].clamp(-1.0, 1.0);
+                let result = match self.bit_depth {
+                    16 => self.writer.write_sample((sample * i16::MAX as f32) as i16),
+                    24 => self.writer.write_sample((sample * 8_388_607.0) as i32),
+                    _ => self.writer.write_sample(sample),
+                };
+                result.map_err(|e| format!("Failed to write WAV data: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn finalize(self) -> Result<(), String> {
+        self.writer.finalize().map_err(|e| format!("Failed to finish WAV file: {}", e))
+    }
+}
+
+/// Write `inputs` back-to-back into one WAV file. All inputs must share a sample
+/// rate and channel count; the output keeps the first file's bit depth when it
+/// is 16/24/32 and uses 16-bit otherwise.
+pub fn concat_files(inputs: &[&Path], output: &Path) -> Result<(), String> {
+    let first = inputs.first().ok_or_else(|| "No files to merge".to_string())?;
+    let first_stream = DecodedStream::open(first)?;
+    let sample_rate = first_stream.sample_rate;
+    let channels = first_stream.channels;
+    let bit_depth = match first_stream.bits_per_sample {
+        Some(bits @ (16 | 24 | 32)) => bits as u16,
+        _ => 16,
+    };
+    drop(first_stream);
+
+    let temp_path = output.with_extension("wav.part");
+    let result = (|| {
+        let mut sink = WavSink::create(&temp_path, sample_rate, channels as u16, bit_depth)?;
+        for input in inputs {
+            let mut stream = DecodedStream::open(input)?;
+            if stream.sample_rate != sample_rate || stream.channels != channels {
+                return Err(format!("{} has a different sample rate or channel count", input.display()));
+            }
+            let mut planar = vec![Vec::new(); channels];
+            while let Some(chunk) = stream.next_chunk()? {
+                remix(&chunk, channels, channels, &mut planar);
+                sink.write_planar(&planar, planar[0].len())?;
+                planar.iter_mut().for_each(Vec::clear);
+            }
+        }
+        sink.finalize()
+    })();
+
+    match result {
+        Ok(()) => fs::rename(&temp_path, output).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            format!("Failed to move merged file into place: {}", e)
+        }),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+/// Writes resampler output, dropping the filter's leading delay and anything
+/// past the expected length
+struct TrimmedWriter {
+    sink: WavSink,
+    to_skip: usize,
+    frames_out: usize,
+}
+
+impl TrimmedWriter {
+    fn write(&mut self, block: &[Vec<f32>], limit: Option<usize>) -> Result<(), String> {
+        let available = block.first().map(|c| c.len()).unwrap_or(0);
+        let skip = self.to_skip.min(available);
+        self.to_skip -= skip;
+        let mut frames = available - skip;
+        if let Some(limit) = limit {
+            frames = frames.min(limit.saturating_sub(self.frames_out));
+        }
+        let trimmed: Vec<Vec<f32>> = block.iter().map(|c| c[skip..skip + frames].to_vec()).collect();
+        self.sink.write_planar(&trimmed, frames)?;
+        self.frames_out += frames;
+        Ok(())
+    }
+}
+
+/// Decode `input` and write it as a WAV matching `target`, resampling and
+/// remixing channels as needed. Samples are streamed, so long recordings never
+/// need to fit in memory. The output appears atomically via a `.part` file.
+pub fn convert_file(input: &Path, output: &Path, target: &AudioTarget) -> Result<(), String> {
+    let temp_path = output.with_extension("wav.part");
+    let result = convert_to(input, &temp_path, target);
+    match result {
+        Ok(()) => fs::rename(&temp_path, output).map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            format!("Failed to move converted file into place: {}", e)
+        }),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+fn convert_to(input: &Path, output: &Path, target: &AudioTarget) -> Result<(), String> {
+    // Input frames fed to the resampler per call
+    const CHUNK_FRAMES: usize = 4096;
+
+    let mut stream = DecodedStream::open(input)?;
+    let out_channels = target.channels as usize;
+    let sink = WavSink::create(output, target.sample_rate, target.channels, target.bit_depth)?;
+
+    let mut resampler = if stream.sample_rate != target.sample_rate {
+        Some(rubato::FftFixedIn::<f32>::new(
+            stream.sample_rate as usize,
+            target.sample_rate as usize,
+            CHUNK_FRAMES,
+            2,
+            out_channels,
+        ).map_err(|e| format!("Failed to create resampler: {}", e))?)
+    } else {
+        None
+    };
+
+    let mut writer = TrimmedWriter {
+        sink,
+        to_skip: resampler.as_ref().map(|r| r.output_delay()).unwrap_or(0),
+        frames_out: 0,
+    };
+    let mut pending: Vec<Vec<f32>> = vec![Vec::new(); out_channels];
+    let mut frames_in: usize = 0;
+
+    while let Some(chunk) = stream.next_chunk()? {
+        frames_in += chunk.len() / stream.channels;
+        remix(&chunk, stream.channels, out_channels, &mut pending);
+
+        match resampler.as_mut() {
+            None => {
+                writer.write(&pending, None)?;
+                pending.iter_mut().for_each(Vec::clear);
+            }
+            Some(resampler) => {
+                while pending[0].len() >= resampler.input_frames_next() {
+                    let needed = resampler.input_frames_next();
+                    let block: Vec<Vec<f32>> = pending.iter_mut().map(|c| c.drain(..needed).collect()).collect();
+                    let out = resampler.process(&block, None)
+                        .map_err(|e| format!("Resampling failed: {}", e))?;
+                    writer.write(&out, None)?;
+                }
+            }
+        }
+    }
+
+    if let Some(resampler) = resampler.as_mut() {
+        // Flush the remaining input and the filter tail up to the expected length
+        let ratio = target.sample_rate as f64 / stream.sample_rate as f64;
+        let expected = (frames_in as f64 * ratio).round() as usize;
+        let out = resampler.process_partial(Some(&pending), None)
+            .map_err(|e| format!("Resampling failed: {}", e))?;
+        writer.write(&out, Some(expected))?;
+        while writer.frames_out < expected {
+            let out = resampler.process_partial::<Vec<f32>>(None, None)
+                .map_err(|e| format!("Resampling failed: {}", e))?;
+            writer.write(&out, Some(expected))?;
+        }
+    }
+
+    writer.sink.finalize()
+}
@@ -0,0 +1,85 @@
+//! Tiling spectrogram thumbnails into a single captioned PNG
+
+use font8x8::{UnicodeFonts, BASIC_FONTS};
+use image::{imageops, Rgb, RgbImage};
+
+const GLYPH_SIZE: u32 = 8;
+const CAPTION_HEIGHT: u32 = GLYPH_SIZE + 6;
+const PADDING: u32 = 4;
+
+const BACKGROUND: Rgb<u8> = Rgb([255, 255, 255]);
+const PLACEHOLDER: Rgb<u8> = Rgb([200, 200, 200]);
+const TEXT: Rgb<u8> = Rgb([0, 0, 0]);
+
+/// Grid of fixed-size tiles, each a thumbnail with a caption strip underneath
+pub struct Sheet {
+    image: RgbImage,
+    columns: u32,
+    thumb_width: u32,
+    thumb_height: u32,
+}
+
+impl Sheet {
+    pub fn new(count: usize, columns: usize, thumb_width: u32, thumb_height: u32) -> Sheet {
+        let columns = columns.clamp(1, count.max(1)) as u32;
+        let rows = (count as u32).div_ceil(columns).max(1);
+        let width = columns * (thumb_width + PADDING) + PADDING;
+        let height = rows * (thumb_height + CAPTION_HEIGHT + PADDING) + PADDING;
+        Sheet {
+            image: RgbImage::from_pixel(width, height, BACKGROUND),
+            columns,
+            thumb_width,
+            thumb_height,
+        }
+    }
+
+    fn tile_origin(&self, index: usize) -> (u32, u32) {
+        let col = index as u32 % self.columns;
+        let row = index as u32 / self.columns;
+        (
+            PADDING + col * (self.thumb_width + PADDING),
+            PADDING + row * (self.thumb_height + CAPTION_HEIGHT + PADDING),
+        )
+    }
+
+    /// Place a thumbnail (PNG bytes) in tile `index`, drawing a grey placeholder
+    /// when the bytes can't be decoded
+    pub fn place(&mut self, index: usize, png: Option<&[u8]>, caption: &str) {
+        let (x, y) = self.tile_origin(index);
+        let thumb = png
+            .and_then(|bytes| image::load_from_memory(bytes).ok())
+            .map(|img| imageops::resize(&img.to_rgb8(), self.thumb_width, self.thumb_height, imageops::FilterType::Triangle))
+            .unwrap_or_else(|| RgbImage::from_pixel(self.thumb_width, self.thumb_height, PLACEHOLDER));
+        imageops::overlay(&mut self.image, &thumb, x as i64, y as i64);
+        self.draw_caption(x, y + self.thumb_height + 3, caption);
+    }
+
+    /// Draw text with the 8x8 bitmap font, truncating to the tile width
+    fn draw_caption(&mut self, x: u32, y: u32, text: &str) {
+        let max_chars = (self.thumb_width / GLYPH_SIZE) as usize;
+        let chars: Vec<char> = text.chars().collect();
+        let visible: Vec<char> = if chars.len() > max_chars && max_chars > 3 {
+            chars[..max_chars - 3].iter().copied().chain("...".chars()).collect()
+        } else {
+            chars.into_iter().take(max_chars).collect()
+        };
+
+        for (i, c) in visible.into_iter().enumerate() {
+            let glyph = BASIC_FONTS.get(c).or_else(|| BASIC_FONTS.get('?')).unwrap_or([0; 8]);
+            let gx = x + i as u32 * GLYPH_SIZE;
+            for (row, bits) in glyph.iter().enumerate() {
+                for col in 0..GLYPH_SIZE {
+                    if bits & (1 << col) != 0 {
+                        self.image.put_pixel(gx + col, y + row as u32, TEXT);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        self.image
+            .save_with_format(path, image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to write contact sheet: {}", e))
+    }
+}
@@ -0,0 +1,52 @@
+//! Structured command errors the frontend can branch on without string matching
+
+use serde::Serialize;
+
+/// Broad category of a command failure. Serialized in snake_case and part of the
+/// frontend contract, so existing variants must not be renamed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The user dismissed a dialog; not worth showing as an error
+    Cancelled,
+    NotFound,
+    AlreadyExists,
+    PermissionDenied,
+    /// Any other filesystem or OS error
+    Io,
+    Other,
+}
+
+/// Error returned by commands, serialized as `{ "kind": ..., "message": ... }`
+#[derive(Debug, Serialize)]
+pub struct AppError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl AppError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        AppError { kind, message: message.into() }
+    }
+
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Cancelled, message)
+    }
+
+    /// Classify an I/O error, prefixing the message with what was being attempted
+    pub fn io(context: &str, error: std::io::Error) -> Self {
+        let kind = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+            std::io::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+            std::io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            _ => ErrorKind::Io,
+        };
+        Self::new(kind, format!("{}: {}", context, error))
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
@@ -0,0 +1,124 @@
+//! Registry of long-running commands, so the frontend can follow and cancel any of
+//! them the same way
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::Emitter;
+
+// Finished jobs kept for `job_status` before the oldest are forgotten
+const FINISHED_JOBS_KEPT: usize = 32;
+
+#[derive(Default)]
+pub struct JobState {
+    jobs: Mutex<HashMap<u64, Arc<Job>>>,
+    next_id: AtomicU64,
+}
+
+/// One background task, shared between its worker threads and the registry
+pub struct Job {
+    id: u64,
+    kind: &'static str,
+    total: usize,
+    done: AtomicUsize,
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// Snapshot of a job; also the `job-progress` event payload
+#[derive(Clone, Serialize)]
+pub struct JobStatus {
+    job_id: u64,
+    /// Which command started the job, e.g. "scan" or "hash"
+    kind: &'static str,
+    done: usize,
+    /// Steps expected; 0 when not known in advance, as for a folder scan
+    total: usize,
+    cancelled: bool,
+    finished: bool,
+}
+
+impl JobState {
+    /// Register a job of `total` steps. A `job-progress` event at zero announces
+    /// its id, so the frontend can cancel it before the command returns.
+    pub fn start(&self, app: &tauri::AppHandle, kind: &'static str, total: usize) -> Arc<Job> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let job = Arc::new(Job {
+            id,
+            kind,
+            total,
+            done: AtomicUsize::new(0),
+            cancelled: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+        });
+
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut finished: Vec<u64> = jobs.values()
+            .filter(|job| job.finished.load(Ordering::SeqCst))
+            .map(|job| job.id)
+            .collect();
+        if finished.len() > FINISHED_JOBS_KEPT {
+            finished.sort_unstable();
+            for id in &finished[..finished.len() - FINISHED_JOBS_KEPT] {
+                jobs.remove(id);
+            }
+        }
+        jobs.insert(id, job.clone());
+        drop(jobs);
+
+        job.emit(app, job.status());
+        job
+    }
+
+    pub fn get(&self, id: u64) -> Option<Arc<Job>> {
+        self.jobs.lock().unwrap().get(&id).cloned()
+    }
+
+    /// Cancel every unfinished job of `kind`
+    pub fn cancel_kind(&self, kind: &str) {
+        for job in self.jobs.lock().unwrap().values() {
+            if job.kind == kind && !job.finished.load(Ordering::SeqCst) {
+                job.cancel();
+            }
+        }
+    }
+}
+
+impl Job {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Count one finished step and emit `job-progress`, returning the steps done
+    pub fn advance(&self, app: &tauri::AppHandle) -> usize {
+        let done = self.done.fetch_add(1, Ordering::SeqCst) + 1;
+        self.emit(app, JobStatus { done, ..self.status() });
+        done
+    }
+
+    /// Mark the job complete (or stopped, if cancelled) and emit a final `job-progress`
+    pub fn finish(&self, app: &tauri::AppHandle) {
+        self.finished.store(true, Ordering::SeqCst);
+        self.emit(app, self.status());
+    }
+
+    pub fn status(&self) -> JobStatus {
+        JobStatus {
+            job_id: self.id,
+            kind: self.kind,
+            done: self.done.load(Ordering::SeqCst),
+            total: self.total,
+            cancelled: self.is_cancelled(),
+            finished: self.finished.load(Ordering::SeqCst),
+        }
+    }
+
+    fn emit(&self, app: &tauri::AppHandle, status: JobStatus) {
+        let _ = app.emit("job-progress", status);
+    }
+}
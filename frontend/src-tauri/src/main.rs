@@ -1,585 +1,5121 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+// Logging macros come before the `mod` declarations so submodules can use them too
+
+/// `println!` that also keeps the line for `export_logs`
+macro_rules! app_println {
+    ($($arg:tt)*) => {{
+        let line = format!($($arg)*);
+        println!("{}", line);
+        $crate::record_app_log(line);
+    }};
+}
+
+/// `eprintln!` that also keeps the line for `export_logs`
+macro_rules! app_eprintln {
+    ($($arg:tt)*) => {{
+        let line = format!($($arg)*);
+        eprintln!("{}", line);
+        $crate::record_app_log(line);
+    }};
+}
+
+mod archive;
+mod audio;
+mod contact_sheet;
+mod error;
+mod jobs;
+mod predictions;
+mod scan;
+mod settings;
+mod shortcuts;
+mod system;
+mod watcher;
+mod window_state;
+
+use error::AppError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::fs;
-use std::net::TcpListener;
-use std::path::PathBuf;
-use std::sync::Mutex;
+use std::io::{BufRead, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tauri_plugin_dialog::DialogExt;
 use tauri_plugin_opener::OpenerExt;
 use tauri_plugin_shell::ShellExt;
 
+// Number of Dipper's own console lines kept for `export_logs`
+const APP_LOG_CAPACITY: usize = 500;
+
+// Dipper's own startup and lifecycle messages, oldest first
+static APP_LOG: Mutex<VecDeque<(std::time::SystemTime, String)>> = Mutex::new(VecDeque::new());
+
+fn record_app_log(line: String) {
+    let mut log = APP_LOG.lock().unwrap();
+    if log.len() == APP_LOG_CAPACITY {
+        log.pop_front();
+    }
+    log.push_back((std::time::SystemTime::now(), line));
+}
+
 // State to store the backend server port and process
 struct BackendState {
     port: Mutex<Option<u16>>,
     process: Mutex<Option<tauri_plugin_shell::process::CommandChild>>,
+    capabilities: Mutex<Option<Capabilities>>,
+    // Set once the app starts shutting down so the supervisor stops respawning
+    shutting_down: AtomicBool,
+    // Set while `restart_backend_server` runs so the supervisor leaves the new child alone
+    restarting: AtomicBool,
+    // Latest startup progress, replayed to the splash if it loads after the event
+    startup_status: Mutex<Option<StartupStatus>>,
+    // Set while the splash shows a startup failure; closing it then quits the app
+    startup_failure: Mutex<Option<StartupFailure>>,
+    // Set once the splash has been swapped for the main window
+    main_shown: AtomicBool,
+}
+
+/// Feature flags advertised by the backend, used to show/hide UI features
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Capabilities {
+    gpu: bool,
+    sse_progress: bool,
+    model_types: Vec<String>,
+    features: Vec<String>,
+}
+
+// State to track in-flight downloads by output path so they can be cancelled
+struct DownloadState {
+    active: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+// Open chunked writes started by `begin_write`, keyed by token. Each write has its
+// own lock so file I/O for one token never holds up the map; it's `None` once
+// `finish_write` has taken it.
+type StreamWriteHandle = Arc<Mutex<Option<StreamWrite>>>;
+
+struct StreamWriteState {
+    writes: Mutex<HashMap<String, StreamWriteHandle>>,
+    next_id: std::sync::atomic::AtomicU64,
+}
+
+struct StreamWrite {
+    path: String,
+    temp_path: String,
+    writer: std::io::BufWriter<fs::File>,
+}
+
+// Folder (canonicalized) that relative output paths are resolved against
+struct ProjectRootState {
+    root: Mutex<Option<PathBuf>>,
+}
+
+// Folders (canonicalized) under which `delete_path` may remove things
+struct OutputRootsState {
+    roots: Mutex<Vec<PathBuf>>,
+}
+
+// Bounds how many `backend_request` calls hit the backend at once
+struct RequestLimiter {
+    semaphore: Arc<tokio::sync::Semaphore>,
+    limit: Mutex<usize>,
+    // Queued requests that can still be cancelled, keyed by caller-supplied id
+    pending: Mutex<HashMap<String, Arc<tokio::sync::Notify>>>,
+}
+
+const MAX_CONCURRENT_REQUESTS_KEY: &str = "max_concurrent_backend_requests";
+const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+// Number of backend output lines kept for `get_server_logs`
+const SERVER_LOG_CAPACITY: usize = 500;
+
+/// One line of backend sidecar output, as emitted in `server-log-batch` events
+#[derive(Clone, Serialize)]
+struct ServerLogLine {
+    /// "stdout", "stderr", "error", or "terminated"
+    stream: &'static str,
+    line: String,
+}
+
+// Most recent backend output, oldest first
+struct ServerLogState {
+    lines: Mutex<VecDeque<ServerLogLine>>,
+    // Lines not yet forwarded; sent together when the batch window closes
+    pending: Mutex<Vec<ServerLogLine>>,
+}
+
+// How long backend output is collected before one `server-log-batch` is emitted
+const SERVER_LOG_BATCH_WINDOW: Duration = Duration::from_millis(100);
+
+// Active folder watchers keyed by folder path; dropping a watcher stops it
+struct WatchState {
+    watchers: Mutex<HashMap<String, notify::RecommendedWatcher>>,
+}
+
+// Cancellation flag for the running `contact_sheet` job
+struct ContactSheetState {
+    cancel: Arc<AtomicBool>,
+}
+
+/// Payload for `contact-sheet-progress` and other done/total progress events
+#[derive(Clone, Serialize)]
+struct Progress {
+    done: usize,
+    total: usize,
+}
+
+// Cancellation flag for the running `normalize_audio_folder` job
+struct NormalizeState {
+    cancel: Arc<AtomicBool>,
+}
+
+/// Per-file outcome of `normalize_audio_folder`
+#[derive(Serialize)]
+struct NormalizeResult {
+    input: String,
+    output: String,
+    error: Option<String>,
+}
+
+/// Result of `normalize_audio_folder`
+#[derive(Serialize)]
+struct NormalizeReport {
+    succeeded: usize,
+    failed: usize,
+    cancelled: bool,
+    results: Vec<NormalizeResult>,
+}
+
+const IO_CONCURRENCY_KEY: &str = "io_concurrency";
+
+/// Number of parallel file workers, from the `io_concurrency` setting or the CPU count
+fn io_concurrency(app: &tauri::AppHandle) -> usize {
+    settings::get::<usize>(app, IO_CONCURRENCY_KEY)
+        .filter(|&n| n > 0)
+        .unwrap_or_else(|| thread::available_parallelism().map(|n| n.get()).unwrap_or(4))
+}
+
+/// Payload for the `download-progress` event
+#[derive(Clone, Serialize)]
+struct DownloadProgress {
+    out_path: String,
+    bytes: u64,
+    total: Option<u64>,
+}
+
+// Settings key mapping each dialog kind to the directory it was last used in
+const LAST_DIALOG_DIRS_KEY: &str = "last_dialog_dirs";
+
+/// Directory the given dialog kind was last used in, if it still exists
+fn last_dialog_dir(app: &tauri::AppHandle, kind: &str) -> Option<PathBuf> {
+    let dirs: HashMap<String, String> = settings::get(app, LAST_DIALOG_DIRS_KEY)?;
+    let dir = PathBuf::from(dirs.get(kind)?);
+    dir.is_dir().then_some(dir)
+}
+
+/// Remember where a dialog kind was last used (best-effort)
+fn remember_dialog_dir(app: &tauri::AppHandle, kind: &str, dir: Option<&Path>) {
+    let Some(dir) = dir else {
+        return;
+    };
+    let mut dirs: HashMap<String, String> = settings::get(app, LAST_DIALOG_DIRS_KEY).unwrap_or_default();
+    dirs.insert(kind.to_string(), dir.to_string_lossy().to_string());
+    if let Err(e) = settings::set(app, LAST_DIALOG_DIRS_KEY, dirs) {
+        app_eprintln!("Failed to remember dialog directory: {}", e);
+    }
+}
+
+/// Show a multi-file picker starting in the directory last used for `kind`
+async fn pick_files_in_last_dir(
+    app: &tauri::AppHandle,
+    mut dialog: tauri_plugin_dialog::FileDialogBuilder<tauri::Wry>,
+    kind: &str,
+) -> Result<Vec<String>, AppError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    if let Some(dir) = last_dialog_dir(app, kind) {
+        dialog = dialog.set_directory(dir);
+    }
+    dialog.pick_files(move |files| {
+        tx.send(files).ok();
+    });
+
+    match rx.await {
+        Ok(Some(paths)) => {
+            let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+            if let Some(first) = paths.first() {
+                remember_dialog_dir(app, kind, Path::new(first).parent());
+            }
+            Ok(paths)
+        }
+        Ok(None) => Err(AppError::cancelled("No files selected")),
+        Err(_) => Err(AppError::from("Failed to receive selection".to_string()))
+    }
+}
+
+/// Result of `select_files`, split by whether each path could be opened
+#[derive(Serialize)]
+struct SelectedFiles {
+    accepted: Vec<String>,
+    rejected: Vec<String>,
+    /// Set when the selection was cut down to `max_files`
+    truncated: bool,
+    /// Number of paths picked in the dialog, before truncation
+    total: usize,
+}
+
+/// Whether a path is a regular file we can actually open for reading
+fn is_readable_file(path: &str) -> bool {
+    fs::metadata(path).is_ok_and(|meta| meta.is_file()) && fs::File::open(path).is_ok()
+}
+
+/// Order for the paths returned by `select_files`
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SortMode {
+    /// Whatever order the dialog reported
+    #[default]
+    AsSelected,
+    /// By file name, character by character
+    Name,
+    /// By file name, comparing digit runs as numbers so "file2" sorts before "file10"
+    NameNatural,
+    /// Oldest modification time first
+    Modified,
+}
+
+fn file_name_of(path: &str) -> &str {
+    Path::new(path).file_name().and_then(|name| name.to_str()).unwrap_or(path)
+}
+
+/// Compare two names with runs of ASCII digits ordered by numeric value
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let end_a = a.find(|c: char| !c.is_ascii_digit()).unwrap_or(a.len());
+            let end_b = b.find(|c: char| !c.is_ascii_digit()).unwrap_or(b.len());
+            let (num_a, num_b) = (a[..end_a].trim_start_matches('0'), b[..end_b].trim_start_matches('0'));
+            // Equal-length digit strings compare lexically the same as numerically
+            let order = num_a.len().cmp(&num_b.len()).then_with(|| num_a.cmp(num_b));
+            if order != Ordering::Equal {
+                return order;
+            }
+            a = &a[end_a..];
+            b = &b[end_b..];
+        } else {
+            if ca != cb {
+                return ca.cmp(&cb);
+            }
+            a = &a[ca.len_utf8()..];
+            b = &b[cb.len_utf8()..];
+        }
+    }
+}
+
+fn sort_paths(paths: &mut [String], sort: SortMode) {
+    match sort {
+        SortMode::AsSelected => {}
+        SortMode::Name => paths.sort_by(|a, b| file_name_of(a).cmp(file_name_of(b))),
+        SortMode::NameNatural => paths.sort_by(|a, b| natural_cmp(file_name_of(a), file_name_of(b))),
+        SortMode::Modified => {
+            // Unreadable files sort last; they end up in `rejected` anyway
+            paths.sort_by_cached_key(|path| {
+                fs::metadata(path).and_then(|meta| meta.modified()).ok().map_or((1, None), |time| (0, Some(time)))
+            });
+        }
+    }
+}
+
+/// Select multiple files.
+/// `extensions` replaces the default "Audio Files" filter list when given, and
+/// `max_files` caps how many paths come back (the rest are dropped and `truncated`
+/// is set). Paths that are not readable regular files (e.g. on an unmounted share)
+/// are returned separately so the UI can report them up front. `primary_extension`
+/// adds a filter for just that format ahead of the others, so the dialog opens
+/// with it selected. `sort` orders the paths before `max_files` is applied
+/// (dialog order by default).
+#[tauri::command]
+async fn select_files(
+    app: tauri::AppHandle,
+    extensions: Option<Vec<String>>,
+    max_files: Option<usize>,
+    primary_extension: Option<String>,
+    sort: Option<SortMode>,
+) -> Result<SelectedFiles, AppError> {
+    let extensions: Vec<&str> = match &extensions {
+        Some(extensions) => extensions.iter().map(|ext| ext.trim_start_matches('.')).collect(),
+        None => audio::AUDIO_EXTENSIONS.to_vec(),
+    };
+    let mut dialog = app.dialog().file();
+    if let Some(primary) = primary_extension.as_deref().map(|ext| ext.trim_start_matches('.')) {
+        dialog = dialog.add_filter(format!("{} Files", primary.to_uppercase()), &[primary]);
+    }
+    let dialog = dialog
+        .add_filter("Audio Files", &extensions)
+        .add_filter("All Files", &["*"]);
+    let mut paths = pick_files_in_last_dir(&app, dialog, "audio").await?;
+    sort_paths(&mut paths, sort.unwrap_or_default());
+    let total = paths.len();
+    let truncated = max_files.is_some_and(|max| total > max);
+    if let Some(max) = max_files {
+        paths.truncate(max);
+    }
+    let (accepted, rejected) = paths.into_iter()
+        .partition(|path| is_readable_file(path));
+    Ok(SelectedFiles { accepted, rejected, truncated, total })
+}
+
+/// Select a single folder
+#[tauri::command]
+async fn select_folder(app: tauri::AppHandle) -> Result<String, AppError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let mut dialog = app.dialog().file();
+    if let Some(dir) = last_dialog_dir(&app, "folder") {
+        dialog = dialog.set_directory(dir);
+    }
+    dialog.pick_folder(move |folder| {
+        tx.send(folder).ok();
+    });
+
+    match rx.await {
+        Ok(Some(path)) => {
+            let path = path.to_string();
+            // Reopen next to the chosen folder so sibling folders are one click away
+            remember_dialog_dir(&app, "folder", Path::new(&path).parent());
+            Ok(path)
+        }
+        Ok(None) => Err(AppError::cancelled("No folder selected")),
+        Err(_) => Err(AppError::from("Failed to receive selection".to_string()))
+    }
+}
+
+/// Select one or more folders, in the order the platform reports them
+#[tauri::command]
+async fn select_folders(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    let mut dialog = app.dialog().file();
+    if let Some(dir) = last_dialog_dir(&app, "folder") {
+        dialog = dialog.set_directory(dir);
+    }
+    dialog.pick_folders(move |folders| {
+        tx.send(folders).ok();
+    });
+
+    match rx.await {
+        Ok(Some(paths)) if !paths.is_empty() => {
+            let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+            remember_dialog_dir(&app, "folder", Path::new(&paths[0]).parent());
+            Ok(paths)
+        }
+        Ok(_) => Err(AppError::cancelled("No folder selected")),
+        Err(_) => Err(AppError::from("Failed to receive selection".to_string()))
+    }
+}
+
+/// Select CSV or PKL files for predictions
+#[tauri::command]
+async fn select_csv_files(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    let dialog = app.dialog()
+        .file()
+        .add_filter("Prediction Files", &["csv", "pkl"])
+        .add_filter("CSV Files", &["csv"])
+        .add_filter("PKL Files", &["pkl"])
+        .add_filter("All Files", &["*"]);
+    pick_files_in_last_dir(&app, dialog, "csv").await
+}
+
+/// Header-level description of a prediction file from `inspect_prediction_files`
+#[derive(Serialize)]
+struct FileSchema {
+    path: String,
+    /// "csv", "pickle", or "unknown"
+    kind: &'static str,
+    size_bytes: Option<u64>,
+    /// CSV header row; pickles are not opened
+    columns: Option<Vec<String>>,
+    error: Option<String>,
+}
+
+/// Describe each selected prediction file without loading it, so the UI can
+/// warn when files from different models (different columns) are mixed
+#[tauri::command]
+async fn inspect_prediction_files(paths: Vec<String>) -> Result<Vec<FileSchema>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        paths.into_iter()
+            .map(|path| {
+                let extension = Path::new(&path)
+                    .extension()
+                    .map(|ext| ext.to_string_lossy().to_lowercase());
+                let kind = match extension.as_deref() {
+                    Some("csv") => "csv",
+                    Some("pkl") | Some("pickle") => "pickle",
+                    _ => "unknown",
+                };
+                let mut schema = FileSchema { path, kind, size_bytes: None, columns: None, error: None };
+
+                match fs::metadata(&schema.path) {
+                    Ok(meta) => schema.size_bytes = Some(meta.len()),
+                    Err(e) => {
+                        schema.error = Some(format!("Failed to read file: {}", e));
+                        return schema;
+                    }
+                }
+                if kind == "csv" {
+                    let columns = csv::Reader::from_path(&schema.path)
+                        .and_then(|mut reader| reader.headers().cloned());
+                    match columns {
+                        Ok(headers) => schema.columns = Some(headers.iter().map(str::to_string).collect()),
+                        Err(e) => schema.error = Some(format!("Failed to read CSV header: {}", e)),
+                    }
+                }
+                schema
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to inspect prediction files: {}", e))
+}
+
+/// Row count of a prediction CSV for display before loading it: exact for files
+/// up to a few hundred MB, estimated from sampled line lengths beyond that
+#[tauri::command]
+async fn estimate_csv_rows(path: String) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || predictions::estimate_rows(Path::new(&path)))
+        .await
+        .map_err(|e| format!("Failed to count CSV rows: {}", e))?
+}
+
+/// Result of `verify_pickle`
+#[derive(Serialize)]
+struct PickleInfo {
+    /// Whether the stream has a well-formed start and ends with STOP
+    valid: bool,
+    /// From the PROTO opcode; `None` for protocol 0/1 streams, which have no header
+    protocol: Option<u8>,
+    /// Why the file was rejected
+    problem: Option<String>,
+}
+
+// Highest pickle protocol Python currently writes
+const MAX_PICKLE_PROTOCOL: u8 = 5;
+
+// Opcodes a protocol 0/1 pickle can plausibly start with
+const UNHEADERED_PICKLE_OPCODES: &[u8] = b"(]})NIJKLMFGSTUVXcdlt";
+
+/// Check the framing of a pickle stream without unpickling it
+fn inspect_pickle(header: &[u8], last_byte: Option<u8>, file_len: u64) -> Result<Option<u8>, String> {
+    let protocol = match header {
+        [] => return Err("File is empty".to_string()),
+        [0x80, protocol, rest @ ..] => {
+            if *protocol > MAX_PICKLE_PROTOCOL {
+                return Err(format!("Unsupported pickle protocol {}", protocol));
+            }
+            // Protocol 4+ streams are framed (except tiny ones); the first frame can't
+            // outrun the file
+            if let (4.., [0x95, len @ ..]) = (*protocol, rest) {
+                let len: [u8; 8] = len.try_into()
+                    .map_err(|_| "File ends inside the first frame header".to_string())?;
+                if u64::from_le_bytes(len) > file_len.saturating_sub(11) {
+                    return Err("File is shorter than its first frame; it may be truncated".to_string());
+                }
+            }
+            Some(*protocol)
+        }
+        [0x80, ..] => return Err("File ends inside the protocol header".to_string()),
+        [opcode, ..] if UNHEADERED_PICKLE_OPCODES.contains(opcode) => None,
+        _ => return Err("Not a pickle file".to_string()),
+    };
+    if last_byte != Some(b'.') {
+        return Err("Pickle does not end with STOP; it may be truncated".to_string());
+    }
+    Ok(protocol)
+}
+
+/// Check that a `.pkl` file is a well-formed pickle before handing it to the
+/// backend. Only the opcode framing at the start and end is read; nothing is
+/// unpickled, so no code in the file runs.
+#[tauri::command]
+async fn verify_pickle(path: String) -> Result<PickleInfo, String> {
+    use std::io::{Seek, SeekFrom};
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut file = fs::File::open(&path)
+            .map_err(|e| format!("Failed to open file: {}", e))?;
+        let file_len = file.metadata()
+            .map_err(|e| format!("Failed to read file: {}", e))?
+            .len();
+
+        // PROTO (2 bytes) plus FRAME and its 8-byte length
+        let mut header = Vec::with_capacity(11);
+        (&mut file).take(11).read_to_end(&mut header)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let mut last_byte = None;
+        if file_len > 0 {
+            let mut byte = [0u8];
+            file.seek(SeekFrom::End(-1))
+                .and_then(|_| file.read_exact(&mut byte))
+                .map_err(|e| format!("Failed to read file: {}", e))?;
+            last_byte = Some(byte[0]);
+        }
+
+        Ok(match inspect_pickle(&header, last_byte, file_len) {
+            Ok(protocol) => PickleInfo { valid: true, protocol, problem: None },
+            Err(problem) => PickleInfo { valid: false, protocol: None, problem: Some(problem) },
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to verify pickle: {}", e))?
+}
+
+/// Select text files
+#[tauri::command]
+async fn select_text_files(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    let dialog = app.dialog()
+        .file()
+        .add_filter("Text Files", &["txt", "csv"])
+        .add_filter("All Files", &["*"]);
+    pick_files_in_last_dir(&app, dialog, "text").await
+}
+
+/// Select JSON files
+#[tauri::command]
+async fn select_json_files(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    let dialog = app.dialog()
+        .file()
+        .add_filter("JSON Files", &["json"])
+        .add_filter("All Files", &["*"]);
+    pick_files_in_last_dir(&app, dialog, "json").await
+}
+
+/// Select model files
+#[tauri::command]
+async fn select_model_files(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    let dialog = app.dialog()
+        .file()
+        .add_filter("Model Files", &["pth", "pt", "pickle", "pkl", "model"])
+        .add_filter("All Files", &["*"]);
+    pick_files_in_last_dir(&app, dialog, "model").await
+}
+
+/// Result of checking one file with `validate_model_files`
+#[derive(Serialize)]
+struct ModelCheck {
+    path: String,
+    /// Whether the contents match a model format expected for the file's extension
+    looks_valid: bool,
+    /// "torch" (zip archive), "pickle", "onnx", or "tflite"
+    detected_format: Option<&'static str>,
+    error: Option<String>,
+}
+
+/// Identify a model format from the first bytes of a file
+fn detect_model_format(header: &[u8], extension: &str) -> Option<&'static str> {
+    match header {
+        // torch.save writes a zip archive since PyTorch 1.6
+        [b'P', b'K', 3, 4, ..] => Some("torch"),
+        // Pickle protocol 2+ opcode; older torch checkpoints are plain pickles
+        [0x80, 2..=5, ..] => Some("pickle"),
+        [_, _, _, _, b'T', b'F', b'L', b'3', ..] => Some("tflite"),
+        // ONNX is a bare protobuf with no magic; it starts with the ir_version field
+        [0x08, ..] if extension == "onnx" => Some("onnx"),
+        _ => None,
+    }
+}
+
+/// Check that each file looks like a model before it is handed to the backend, by
+/// comparing its leading bytes with the formats its extension implies. Catches text
+/// files and other obviously wrong picks; it does not load the model.
+#[tauri::command]
+async fn validate_model_files(paths: Vec<String>) -> Result<Vec<ModelCheck>, String> {
+    tauri::async_runtime::spawn_blocking(move || paths.into_iter().map(check_model_file).collect())
+        .await
+        .map_err(|e| format!("Failed to validate model files: {}", e))
+}
+
+fn check_model_file(path: String) -> ModelCheck {
+    let extension = Path::new(&path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mut header = Vec::with_capacity(8);
+    let read = fs::File::open(&path).and_then(|file| file.take(8).read_to_end(&mut header));
+    if let Err(e) = read {
+        return ModelCheck {
+            path,
+            looks_valid: false,
+            detected_format: None,
+            error: Some(format!("Failed to read file: {}", e)),
+        };
+    }
+
+    let detected_format = detect_model_format(&header, &extension);
+    let looks_valid = match (extension.as_str(), detected_format) {
+        ("pt" | "pth" | "model" | "pkl" | "pickle", Some("torch" | "pickle")) => true,
+        ("onnx", Some("onnx")) | ("tflite", Some("tflite")) => true,
+        ("pt" | "pth" | "model" | "pkl" | "pickle" | "onnx" | "tflite", _) => false,
+        // Unfamiliar extension: trust the contents
+        (_, detected) => detected.is_some(),
+    };
+    ModelCheck { path, looks_valid, detected_format, error: None }
+}
+
+/// Show save file dialog and return the selected path.
+/// The dialog opens where the previous save went (tracked apart from the open
+/// dialogs), so consecutive exports land in the same folder.
+#[tauri::command]
+async fn save_file(app: tauri::AppHandle, default_name: String) -> Result<String, AppError> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+
+    // Determine file type from the actual extension, not a substring of the name
+    let extension = Path::new(&default_name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+
+    let mut dialog = app.dialog()
+        .file()
+        .set_file_name(&default_name);
+    if let Some(dir) = last_dialog_dir(&app, "save") {
+        dialog = dialog.set_directory(dir);
+    }
+
+    match extension.as_deref() {
+        Some("json") => dialog = dialog.add_filter("JSON Files", &["json"]),
+        Some("csv") => dialog = dialog.add_filter("CSV Files", &["csv"]),
+        Some("txt") => dialog = dialog.add_filter("Text Files", &["txt"]),
+        _ => {}
+    }
+    dialog = dialog.add_filter("All Files", &["*"]);
+
+    dialog.save_file(move |path| {
+        tx.send(path).ok();
+    });
+
+    match rx.await {
+        Ok(Some(p)) => {
+            let path = p.to_string();
+            if let Some(dir) = Path::new(&path).parent() {
+                record_recent_output(&app, dir);
+                remember_dialog_dir(&app, "save", Some(dir));
+            }
+            Ok(path)
+        }
+        Ok(None) => Err(AppError::cancelled("Save cancelled")),
+        Err(_) => Err(AppError::from("Failed to receive selection".to_string()))
+    }
+}
+
+/// Resolve an output path in a known folder without showing a dialog.
+/// When `overwrite` is false and the file exists, `_1`, `_2`, ... is inserted before
+/// the extension (as `generate_unique_folder_name` does for folders). `file_name`
+/// must be a bare name, so the result always stays in `dir` (and so inside the
+/// project root when `dir` is relative to it).
+#[tauri::command]
+async fn save_file_to(app: tauri::AppHandle, dir: String, file_name: String, overwrite: bool) -> Result<String, String> {
+    let mut components = Path::new(&file_name).components();
+    if !matches!((components.next(), components.next()), (Some(std::path::Component::Normal(_)), None)) {
+        return Err(format!("Invalid file name: {:?}", file_name));
+    }
+    let dir = resolve_project_path(&app, &dir)?;
+    if !dir.is_dir() {
+        return Err(format!("Folder does not exist: {}", dir.display()));
+    }
+
+    let path = if overwrite { dir.join(&file_name) } else { unique_file_path(&dir, &file_name) };
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Resolve the relative output paths passed to `write_file`, `save_file_to`, and
+/// the folder-creation commands against this folder from now on
+#[tauri::command]
+async fn set_project_root(project_root: tauri::State<'_, ProjectRootState>, path: String) -> Result<(), String> {
+    let root = fs::canonicalize(&path)
+        .map_err(|e| format!("Failed to resolve project root {}: {}", path, e))?;
+    if !root.is_dir() {
+        return Err(format!("Project root is not a folder: {}", path));
+    }
+    *project_root.root.lock().unwrap() = Some(root);
+    Ok(())
+}
+
+/// Resolve `path` against the project root. Absolute paths, and any path while no
+/// root is set, pass through unchanged. A relative path must stay inside the root
+/// once `..` and symlinks in its existing part are resolved.
+fn resolve_project_path(app: &tauri::AppHandle, path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(path);
+    let state: tauri::State<ProjectRootState> = app.state();
+    let root = match state.root.lock().unwrap().clone() {
+        Some(root) if relative.is_relative() => root,
+        _ => return Ok(relative.to_path_buf()),
+    };
+
+    let escapes = || format!("Path escapes the project root: {}", path);
+    let mut resolved = root.clone();
+    for component in relative.components() {
+        match component {
+            std::path::Component::Normal(part) => resolved.push(part),
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir if resolved != root => {
+                resolved.pop();
+            }
+            _ => return Err(escapes()),
+        }
+    }
+
+    // Follow symlinks in whatever part of the path already exists
+    let existing = resolved.ancestors()
+        .find(|p| p.exists())
+        .unwrap_or(&root);
+    let remainder = resolved.strip_prefix(existing).unwrap_or(Path::new(""));
+    let resolved = fs::canonicalize(existing)
+        .map_err(|e| format!("Failed to resolve {}: {}", path, e))?
+        .join(remainder);
+    if !resolved.starts_with(&root) {
+        return Err(escapes());
+    }
+    Ok(resolved)
+}
+
+/// `dir/file_name`, or `dir/stem_N.ext` with the first free N if that is taken
+fn unique_file_path(dir: &Path, file_name: &str) -> PathBuf {
+    let mut path = dir.join(file_name);
+    let name = Path::new(file_name);
+    let stem = name.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let extension = name.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{}_{}{}", stem, counter, extension));
+        counter += 1;
+    }
+    path
+}
+
+/// Write content to a file.
+/// The content goes to a sibling `<name>.tmp` first and is renamed over the target,
+/// so an interrupted write never leaves a truncated file behind. An existing file is
+/// only replaced when `overwrite` is true; otherwise an `already_exists` error is
+/// returned so the UI can ask before retrying. Missing parent folders are created
+/// unless `create_parents` is false.
+#[tauri::command]
+async fn write_file(
+    app: tauri::AppHandle,
+    file_path: String,
+    content: String,
+    overwrite: Option<bool>,
+    create_parents: Option<bool>,
+) -> Result<(), AppError> {
+    let file_path = resolve_project_path(&app, &file_path)?;
+    if !overwrite.unwrap_or(false) && file_path.exists() {
+        return Err(AppError::new(error::ErrorKind::AlreadyExists, format!("File already exists: {}", file_path.display())));
+    }
+    if create_parents.unwrap_or(true) {
+        if let Some(parent) = file_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .map_err(|e| AppError::io(&format!("Failed to create folder {}", parent.display()), e))?;
+        }
+    }
+    write_atomically(&file_path, content.as_bytes())
+        .map_err(|e| AppError::io("Failed to write file", e))
+}
+
+/// Write `data` to a uniquely named sibling `<name>.<pid>-<n>.tmp` and rename it
+/// over `file_path`. The temp file is created fresh, so concurrent writes to the same
+/// target don't share one and an existing file is never truncated.
+fn write_atomically(file_path: &Path, data: &[u8]) -> std::io::Result<()> {
+    static NEXT_TEMP_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    let (temp_path, mut file) = loop {
+        let mut temp_path = file_path.as_os_str().to_owned();
+        temp_path.push(format!(".{}-{}.tmp", std::process::id(), NEXT_TEMP_ID.fetch_add(1, Ordering::SeqCst)));
+        let temp_path = PathBuf::from(temp_path);
+        match fs::OpenOptions::new().write(true).create_new(true).open(&temp_path) {
+            Ok(file) => break (temp_path, file),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    };
+    file.write_all(data)
+        .and_then(|_| file.sync_all())
+        .and_then(|_| fs::rename(&temp_path, file_path))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&temp_path);
+        })
+}
+
+/// Serialize a JSON value on the Rust side and write it atomically. With `pretty`
+/// the output is indented with two spaces, giving stable, diffable config files;
+/// either way it ends with a newline.
+#[tauri::command]
+async fn write_json_file(path: String, value: serde_json::Value, pretty: bool) -> Result<(), AppError> {
+    let mut json = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    }
+    .map_err(|e| AppError::from(format!("Failed to serialize JSON: {}", e)))?;
+    json.push('\n');
+    write_atomically(Path::new(&path), json.as_bytes())
+        .map_err(|e| AppError::io("Failed to write file", e))
+}
+
+/// Append content to a file, creating it if missing
+#[tauri::command]
+async fn append_file(file_path: String, content: String) -> Result<(), AppError> {
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&file_path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| AppError::io("Failed to write file", e))
+}
+
+/// Start a chunked write to `file_path` and return a token for `write_chunk`/`finish_write`.
+/// Data goes to a sibling `.part` file and only replaces the target on `finish_write`.
+#[tauri::command]
+async fn begin_write(writes: tauri::State<'_, StreamWriteState>, file_path: String) -> Result<String, String> {
+    let temp_path = format!("{}.part", file_path);
+    let write = tauri::async_runtime::spawn_blocking(move || {
+        let file = fs::File::create(&temp_path)
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+        Ok::<_, String>(StreamWrite {
+            path: file_path,
+            temp_path,
+            writer: std::io::BufWriter::new(file),
+        })
+    })
+    .await
+    .map_err(|e| format!("Failed to write file: {}", e))??;
+    let token = format!("write-{}", writes.next_id.fetch_add(1, Ordering::SeqCst));
+    writes.writes.lock().unwrap().insert(token.clone(), Arc::new(Mutex::new(Some(write))));
+    Ok(token)
+}
+
+// The handle for `token`, cloned out so the map lock isn't held during I/O
+fn stream_write_handle(writes: &StreamWriteState, token: &str) -> Result<StreamWriteHandle, String> {
+    writes.writes.lock().unwrap()
+        .get(token)
+        .cloned()
+        .ok_or_else(|| format!("Unknown write token: {}", token))
+}
+
+/// Append data to a write started with `begin_write`
+#[tauri::command]
+async fn write_chunk(writes: tauri::State<'_, StreamWriteState>, token: String, data: String) -> Result<(), String> {
+    let handle = stream_write_handle(&writes, &token)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut write = handle.lock().unwrap();
+        let write = write.as_mut()
+            .ok_or_else(|| format!("Unknown write token: {}", token))?;
+        write.writer.write_all(data.as_bytes())
+            .map_err(|e| format!("Failed to write file: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to write file: {}", e))?
+}
+
+/// Flush a chunked write and move it into place
+#[tauri::command]
+async fn finish_write(writes: tauri::State<'_, StreamWriteState>, token: String) -> Result<(), String> {
+    let handle = writes.writes.lock().unwrap()
+        .remove(&token)
+        .ok_or_else(|| format!("Unknown write token: {}", token))?;
+    tauri::async_runtime::spawn_blocking(move || {
+        // Waits for any `write_chunk` still in flight for this token
+        let StreamWrite { path, temp_path, writer } = handle.lock().unwrap()
+            .take()
+            .ok_or_else(|| format!("Unknown write token: {}", token))?;
+        writer.into_inner()
+            .map_err(|e| e.into_error())
+            .and_then(|file| file.sync_all())
+            .and_then(|_| fs::rename(&temp_path, &path))
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                format!("Failed to write file: {}", e)
+            })
+    })
+    .await
+    .map_err(|e| format!("Failed to write file: {}", e))?
+}
+
+/// Drop chunked writes that were never finished and delete their partial files
+fn discard_unfinished_writes(app: &tauri::AppHandle) {
+    let writes: tauri::State<StreamWriteState> = app.state();
+    for (_, handle) in writes.writes.lock().unwrap().drain() {
+        if let Some(write) = handle.lock().unwrap().take() {
+            drop(write.writer);
+            let _ = fs::remove_file(&write.temp_path);
+        }
+    }
+}
+
+/// Line terminator for written CSV files
+#[derive(Clone, Copy, Default, Deserialize)]
+enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+/// Formatting options shared by the CSV-writing commands
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+struct CsvOptions {
+    line_ending: LineEnding,
+    /// Prefix the file with a UTF-8 byte order mark so Excel detects the encoding
+    utf8_bom: bool,
+}
+
+// UTF-8 byte order mark
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Write `rows` as CSV records to `out`
+fn write_csv_rows<W: Write>(out: W, rows: &[Vec<String>], options: &CsvOptions) -> Result<(), String> {
+    let terminator = match options.line_ending {
+        LineEnding::Lf => csv::Terminator::Any(b'\n'),
+        LineEnding::Crlf => csv::Terminator::CRLF,
+    };
+    let mut writer = csv::WriterBuilder::new()
+        .terminator(terminator)
+        .flexible(true)
+        .from_writer(out);
+    for row in rows {
+        writer.write_record(row)
+            .map_err(|e| format!("Failed to write CSV row: {}", e))?;
+    }
+    writer.flush().map_err(|e| format!("Failed to write file: {}", e))
+}
+
+/// Write rows (header first) to a new CSV file, replacing any existing file
+#[tauri::command]
+async fn write_csv(file_path: String, rows: Vec<Vec<String>>, options: Option<CsvOptions>) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+    let mut file = fs::File::create(&file_path)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    if options.utf8_bom {
+        file.write_all(UTF8_BOM).map_err(|e| format!("Failed to write file: {}", e))?;
+    }
+    write_csv_rows(file, &rows, &options)
+}
+
+/// Append rows to a CSV file, creating it (with a BOM if requested) when missing
+#[tauri::command]
+async fn append_csv(file_path: String, rows: Vec<Vec<String>>, options: Option<CsvOptions>) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&file_path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let is_new = file.metadata().map(|m| m.len() == 0).unwrap_or(false);
+    if is_new && options.utf8_bom {
+        file.write_all(UTF8_BOM).map_err(|e| format!("Failed to write file: {}", e))?;
+    }
+    write_csv_rows(file, &rows, &options)
+}
+
+/// Open a file with the system default application
+#[tauri::command]
+async fn open_file(app: tauri::AppHandle, file_path: String) -> Result<(), String> {
+    app.opener()
+        .open_path(&file_path, None::<&str>)
+        .map_err(|e| format!("Failed to open file: {}", e))
+}
+
+/// Show a file or folder in Finder / Explorer / the Linux file manager.
+/// Linux has no portable "select this item" call, so the containing folder is opened.
+#[tauri::command]
+async fn reveal_in_file_manager(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let target = Path::new(&path);
+    if !target.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+
+    let command = if cfg!(target_os = "macos") {
+        app.shell().command("open").args(["-R", &path])
+    } else if cfg!(target_os = "windows") {
+        // Passed as two arguments so paths with spaces survive Windows quoting
+        app.shell().command("explorer").args(["/select,", &path])
+    } else {
+        let folder = if target.is_dir() { target } else { target.parent().unwrap_or(target) };
+        app.shell().command("xdg-open").arg(folder)
+    };
+    command.spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open file manager: {}", e))
+}
+
+// Local file types `open_external` will hand to the default application
+const EXTERNAL_DOCUMENT_EXTENSIONS: &[&str] = &[
+    "html", "htm", "pdf", "txt", "md", "csv", "json", "png", "jpg", "jpeg", "svg",
+];
+
+/// Open an http(s) URL in the default browser, or a local document (report, help
+/// page) in its default application. Local targets must be existing files of a
+/// document type inside a registered output root (whether given as a plain path or
+/// a `file://` URL), so this can't be used to launch executables.
+#[tauri::command]
+async fn open_external(
+    app: tauri::AppHandle,
+    output_roots: tauri::State<'_, OutputRootsState>,
+    target: String,
+) -> Result<(), String> {
+    let lower = target.to_lowercase();
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        return app.opener()
+            .open_url(&target, None::<&str>)
+            .map_err(|e| format!("Failed to open URL: {}", e));
+    }
+
+    let path = if lower.starts_with("file://") {
+        tauri::Url::parse(&target)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .ok_or_else(|| format!("Invalid file URL: {}", target))?
+    } else if lower.contains("://") {
+        return Err(format!("Unsupported URL scheme: {}", target));
+    } else {
+        PathBuf::from(&target)
+    };
+    let path = fs::canonicalize(&path)
+        .map_err(|e| format!("Failed to open {}: {}", target, e))?;
+    let in_root = output_roots.roots.lock().unwrap()
+        .iter()
+        .any(|root| path.starts_with(root));
+    if !in_root {
+        return Err(format!("Refusing to open {}: not inside a registered output folder", target));
+    }
+
+    if !path.is_file() {
+        return Err(format!("Not a file: {}", path.display()));
+    }
+    let is_document = path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| EXTERNAL_DOCUMENT_EXTENSIONS.contains(&ext.as_str()));
+    if !is_document {
+        return Err(format!("Refusing to open {}: not a document type", path.display()));
+    }
+
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open file: {}", e))
+}
+
+/// Put the given paths on the system clipboard, one per line
+#[tauri::command]
+async fn copy_paths_to_clipboard(app: tauri::AppHandle, paths: Vec<String>) -> Result<(), String> {
+    app.clipboard()
+        .write_text(paths.join("\n"))
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
+/// Result of `read_text_file`
+#[derive(Serialize)]
+struct TextFile {
+    content: String,
+    /// Encoding the file was decoded with; a byte order mark overrides the requested one
+    encoding: &'static str,
+    /// Set when some bytes were invalid and replaced with U+FFFD, so characters may
+    /// be wrong
+    lossy: bool,
+}
+
+/// Read text content from a file, transcoded to UTF-8. `encoding` is a label such
+/// as "utf-8" (the default), "latin1", or "windows-1252"; bytes that are invalid in
+/// it are replaced rather than failing the read, and `lossy` reports that.
+#[tauri::command]
+async fn read_text_file(file_path: String, encoding: Option<String>) -> Result<TextFile, AppError> {
+    let label = encoding.as_deref().unwrap_or("utf-8");
+    let requested = encoding_rs::Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| AppError::from(format!("Unsupported text encoding: {}", label)))?;
+    let bytes = fs::read(&file_path)
+        .map_err(|e| AppError::io("Failed to read file", e))?;
+    let (content, used, lossy) = requested.decode(&bytes);
+    Ok(TextFile { content: content.into_owned(), encoding: used.name(), lossy })
+}
+
+/// Read at most the first `lines` lines of a text file, for previewing files too
+/// large to load whole. Line endings are kept as they are in the file; a shorter
+/// file simply yields fewer lines.
+#[tauri::command]
+async fn read_file_head(path: String, lines: usize) -> Result<String, AppError> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let file = fs::File::open(&path).map_err(|e| AppError::io("Failed to read file", e))?;
+        let mut reader = std::io::BufReader::new(file);
+        let mut head = Vec::new();
+        for _ in 0..lines {
+            let read = reader.read_until(b'\n', &mut head)
+                .map_err(|e| AppError::io("Failed to read file", e))?;
+            if read == 0 {
+                break;
+            }
+        }
+        Ok(String::from_utf8_lossy(&head).into_owned())
+    })
+    .await
+    .map_err(|e| AppError::from(format!("Failed to read file: {}", e)))?
+}
+
+/// Generate a unique folder name by appending numeric suffix if needed
+#[tauri::command]
+async fn generate_unique_folder_name(app: tauri::AppHandle, base_path: String, folder_name: String) -> Result<String, String> {
+    let base = resolve_project_path(&app, &base_path)?;
+
+    // Check if base path exists
+    if !base.exists() {
+        return Err(format!("Base path does not exist: {}", base_path));
+    }
+
+    let mut unique_name = folder_name.clone();
+    let mut counter = 1;
+
+    loop {
+        let test_path = base.join(&unique_name);
+        if !test_path.exists() {
+            return Ok(unique_name);
+        }
+        unique_name = format!("{}_{}", folder_name, counter);
+        counter += 1;
+    }
+}
+
+/// Create a new folder under `base_path`, appending `_1`, `_2`, ... to the name until
+/// the creation succeeds. Unlike `generate_unique_folder_name` the folder exists when
+/// this returns, so concurrent runs can never be handed the same one.
+#[tauri::command]
+async fn create_unique_folder(app: tauri::AppHandle, base_path: String, folder_name: String) -> Result<String, String> {
+    let base = resolve_project_path(&app, &base_path)?;
+    if !base.is_dir() {
+        return Err(format!("Base path does not exist: {}", base_path));
+    }
+
+    let mut unique_name = folder_name.clone();
+    let mut counter = 1;
+
+    loop {
+        let path = base.join(&unique_name);
+        match fs::create_dir(&path) {
+            Ok(()) => {
+                record_recent_output(&app, &path);
+                return Ok(path.to_string_lossy().to_string());
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                unique_name = format!("{}_{}", folder_name, counter);
+                counter += 1;
+            }
+            Err(e) => return Err(format!("Failed to create folder: {}", e)),
+        }
+    }
+}
+
+// Settings key for output folders recently used under a registered output root
+const RECENT_OUTPUTS_KEY: &str = "recent_outputs";
+const RECENT_OUTPUTS_LIMIT: usize = 10;
+
+/// Put the existing folder `dir` at the front of the recent outputs if it lies
+/// inside a registered output root
+fn record_recent_output(app: &tauri::AppHandle, dir: &Path) {
+    let Ok(resolved) = fs::canonicalize(dir) else {
+        return;
+    };
+    let output_roots: tauri::State<OutputRootsState> = app.state();
+    let in_root = output_roots.roots.lock().unwrap()
+        .iter()
+        .any(|root| resolved.starts_with(root));
+    if !in_root {
+        return;
+    }
+
+    let resolved = resolved.to_string_lossy().to_string();
+    let mut recent: Vec<String> = settings::get(app, RECENT_OUTPUTS_KEY).unwrap_or_default();
+    recent.retain(|dir| *dir != resolved && Path::new(dir).is_dir());
+    recent.insert(0, resolved);
+    recent.truncate(RECENT_OUTPUTS_LIMIT);
+    if let Err(e) = settings::set(app, RECENT_OUTPUTS_KEY, recent) {
+        app_eprintln!("Failed to remember recent output folder: {}", e);
+    }
+}
+
+/// Output folders recently created or saved into, newest first. Folders that
+/// have since been deleted are left out.
+#[tauri::command]
+async fn get_recent_outputs(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+    let recent: Vec<String> = settings::get(&app, RECENT_OUTPUTS_KEY).unwrap_or_default();
+    Ok(recent.into_iter().filter(|dir| Path::new(dir).is_dir()).collect())
+}
+
+/// Forget all recent output folders
+#[tauri::command]
+async fn clear_recent_outputs(app: tauri::AppHandle) -> Result<(), String> {
+    settings::set(&app, RECENT_OUTPUTS_KEY, Vec::<String>::new())
+}
+
+/// Allow `delete_path` to remove files and folders inside `path`
+#[tauri::command]
+async fn register_output_root(output_roots: tauri::State<'_, OutputRootsState>, path: String) -> Result<(), String> {
+    let root = fs::canonicalize(&path)
+        .map_err(|e| format!("Failed to resolve output root {}: {}", path, e))?;
+    if !root.is_dir() {
+        return Err(format!("Output root is not a folder: {}", path));
+    }
+    let mut roots = output_roots.roots.lock().unwrap();
+    if !roots.contains(&root) {
+        roots.push(root);
+    }
+    Ok(())
+}
+
+/// Delete a file or folder, but only strictly inside a registered output root.
+/// The parent is canonicalized and the final component kept as-is, so a symlink is
+/// judged (and removed) by where the link lives, never by what it points to.
+#[tauri::command]
+async fn delete_path(output_roots: tauri::State<'_, OutputRootsState>, path: String, recursive: bool) -> Result<(), String> {
+    let target = Path::new(&path);
+    let (Some(parent), Some(name)) = (target.parent(), target.file_name()) else {
+        return Err(format!("Refusing to delete {}", path));
+    };
+    let parent = if parent.as_os_str().is_empty() { Path::new(".") } else { parent };
+    let resolved = fs::canonicalize(parent)
+        .map_err(|e| format!("Failed to resolve {}: {}", path, e))?
+        .join(name);
+
+    let allowed = output_roots.roots.lock().unwrap()
+        .iter()
+        .any(|root| resolved.starts_with(root) && resolved != *root);
+    if !allowed {
+        return Err(format!("Refusing to delete {}: not inside a registered output folder", path));
+    }
+
+    let meta = fs::symlink_metadata(&resolved)
+        .map_err(|e| format!("Failed to delete {}: {}", path, e))?;
+    let result = if meta.is_dir() {
+        if recursive { fs::remove_dir_all(&resolved) } else { fs::remove_dir(&resolved) }
+    } else {
+        fs::remove_file(&resolved)
+    };
+    result.map_err(|e| format!("Failed to delete {}: {}", path, e))
+}
+
+/// One rename requested through `rename_files`
+#[derive(Deserialize)]
+struct RenameOp {
+    from: String,
+    to: String,
+}
+
+/// Outcome of one `RenameOp`
+#[derive(Serialize)]
+struct RenameResult {
+    from: String,
+    to: String,
+    /// `kind` is `already_exists` when the target was left in place
+    error: Option<AppError>,
+}
+
+/// Rename files in bulk. Each op is applied and reported on its own, so one
+/// collision doesn't stop the rest; existing targets are only replaced when
+/// `overwrite` is true.
+#[tauri::command]
+async fn rename_files(ops: Vec<RenameOp>, overwrite: Option<bool>) -> Result<Vec<RenameResult>, String> {
+    let overwrite = overwrite.unwrap_or(false);
+    Ok(ops.into_iter()
+        .map(|RenameOp { from, to }| {
+            let error = if !Path::new(&from).exists() {
+                Some(AppError::new(error::ErrorKind::NotFound, format!("File does not exist: {}", from)))
+            } else if !overwrite && Path::new(&to).exists() {
+                Some(AppError::new(error::ErrorKind::AlreadyExists, format!("File already exists: {}", to)))
+            } else {
+                fs::rename(&from, &to)
+                    .err()
+                    .map(|e| AppError::io("Failed to rename file", e))
+            };
+            RenameResult { from, to, error }
+        })
+        .collect())
+}
+
+/// Whether `gather_files` leaves the originals in place
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CopyMode {
+    Copy,
+    Move,
+}
+
+/// Outcome of gathering one file; `to` is where it ended up
+#[derive(Serialize)]
+struct GatherResult {
+    from: String,
+    to: Option<String>,
+    error: Option<String>,
+}
+
+/// Copy or move files into `dest_dir`, suffixing names that collide with files
+/// already there (or gathered earlier in the same call). Moves across filesystems
+/// fall back to copy-then-delete. Each file is reported on its own.
+#[tauri::command]
+async fn gather_files(paths: Vec<String>, dest_dir: String, mode: CopyMode) -> Result<Vec<GatherResult>, String> {
+    let dest = PathBuf::from(&dest_dir);
+    if !dest.is_dir() {
+        return Err(format!("Folder does not exist: {}", dest_dir));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        paths.into_iter()
+            .map(|from| {
+                let source = Path::new(&from);
+                let Some(file_name) = source.file_name().filter(|_| source.is_file()) else {
+                    return GatherResult { error: Some(format!("File does not exist: {}", from)), from, to: None };
+                };
+                let target = unique_file_path(&dest, &file_name.to_string_lossy());
+                let result = match mode {
+                    CopyMode::Copy => fs::copy(source, &target).map(|_| ()),
+                    CopyMode::Move => match fs::rename(source, &target) {
+                        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                            fs::copy(source, &target).and_then(|_| fs::remove_file(source))
+                        }
+                        other => other,
+                    },
+                };
+                match result {
+                    Ok(()) => GatherResult { from, to: Some(target.to_string_lossy().to_string()), error: None },
+                    Err(e) => GatherResult { error: Some(format!("Failed to gather file: {}", e)), from, to: None },
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Gather task failed: {}", e))
+}
+
+/// Move a finished output folder into `archive_root`, either as a `<name>.tar.gz`
+/// (with `compress`) or as the folder itself, suffixing the name if it is taken.
+/// The original is deleted only after the archive or copy has been read back and
+/// holds as many files as the source. Returns the archive or folder path.
+#[tauri::command]
+async fn archive_output(source_dir: String, archive_root: String, compress: bool) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let source = fs::canonicalize(&source_dir)
+            .map_err(|e| format!("Failed to read folder {}: {}", source_dir, e))?;
+        if !source.is_dir() {
+            return Err(format!("Not a folder: {}", source_dir));
+        }
+        let root = fs::canonicalize(&archive_root)
+            .map_err(|e| format!("Archive folder does not exist: {} ({})", archive_root, e))?;
+        if root.starts_with(&source) {
+            return Err("The archive folder can't be inside the folder being archived".to_string());
+        }
+        let name = source.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Can't archive {}", source_dir))?;
+        let unique = |extension: &str| {
+            let mut path = root.join(format!("{}{}", name, extension));
+            let mut counter = 1;
+            while path.exists() {
+                path = root.join(format!("{}_{}{}", name, counter, extension));
+                counter += 1;
+            }
+            path
+        };
+
+        let expected = archive::count_files(&source)
+            .map_err(|e| format!("Failed to read folder: {}", e))?;
+        let target = if compress {
+            let target = unique(".tar.gz");
+            archive::write_tar_gz(&source, &target)
+                .map_err(|e| format!("Failed to write archive: {}", e))?;
+            let written = archive::count_tar_gz_files(&target)
+                .inspect_err(|_| {
+                    let _ = fs::remove_file(&target);
+                })
+                .map_err(|e| format!("Failed to verify archive: {}", e))?;
+            if written != expected {
+                let _ = fs::remove_file(&target);
+                return Err(format!("Archive holds {} files but the folder has {}; the original was kept", written, expected));
+            }
+            target
+        } else {
+            let target = unique("");
+            match fs::rename(&source, &target) {
+                Ok(()) => return Ok(target.to_string_lossy().to_string()),
+                Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {}
+                Err(e) => return Err(format!("Failed to move folder: {}", e)),
+            }
+            archive::copy_tree(&source, &target)
+                .map_err(|e| format!("Failed to copy folder: {}", e))?;
+            let copied = archive::count_files(&target)
+                .inspect_err(|_| {
+                    let _ = fs::remove_dir_all(&target);
+                })
+                .map_err(|e| format!("Failed to verify copy: {}", e))?;
+            if copied != expected {
+                let _ = fs::remove_dir_all(&target);
+                return Err(format!("Copy holds {} files but the folder has {}; the original was kept", copied, expected));
+            }
+            target
+        };
+
+        fs::remove_dir_all(&source)
+            .map_err(|e| format!("Archived to {} but failed to remove the original: {}", target.display(), e))?;
+        Ok(target.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("Archive task failed: {}", e))?
+}
+
+/// Space on the filesystem holding a path, in bytes
+#[derive(Serialize)]
+struct DiskSpace {
+    available: u64,
+    total: u64,
+}
+
+/// Report free and total space on the filesystem that contains `path`.
+/// The path itself may not exist yet; its nearest existing ancestor is used.
+#[tauri::command]
+async fn check_free_space(path: String) -> Result<DiskSpace, String> {
+    let existing = Path::new(&path)
+        .ancestors()
+        .find(|p| p.exists())
+        .ok_or_else(|| format!("No existing parent folder for {}", path))?;
+    let (available, total) = system::disk_space(existing)
+        .ok_or_else(|| format!("Failed to determine disk space for {}", path))?;
+    Ok(DiskSpace { available, total })
+}
+
+/// Result of `validate_output_folder`
+#[derive(Serialize)]
+struct OutputFolderCheck {
+    path: String,
+    exists: bool,
+    is_dir: bool,
+    writable: bool,
+    /// Human-readable reason when the folder can't be used
+    message: Option<String>,
+}
+
+/// Check that a folder exists, is a directory, and accepts writes. Writability is
+/// probed by creating and removing a small file, which catches read-only mounts
+/// (DMGs, archived network shares) that permission bits alone don't reveal.
+#[tauri::command]
+async fn validate_output_folder(path: String) -> Result<OutputFolderCheck, String> {
+    Ok(check_output_folder(path))
+}
+
+fn check_output_folder(path: String) -> OutputFolderCheck {
+    let folder = PathBuf::from(&path);
+    let mut check = OutputFolderCheck {
+        path: path.clone(),
+        exists: folder.exists(),
+        is_dir: folder.is_dir(),
+        writable: false,
+        message: None,
+    };
+
+    if !check.exists {
+        check.message = Some(format!("Folder does not exist: {}", path));
+    } else if !check.is_dir {
+        check.message = Some(format!("Not a folder: {}", path));
+    } else {
+        let probe = folder.join(format!(".dipper_write_test_{}", std::process::id()));
+        match fs::write(&probe, b"") {
+            Ok(()) => {
+                let _ = fs::remove_file(&probe);
+                check.writable = true;
+            }
+            Err(e) => {
+                check.message = Some(format!("Folder is not writable: {}", e));
+            }
+        }
+    }
+    check
+}
+
+/// One line of the `preflight_run` checklist
+#[derive(Serialize)]
+struct PreflightCheck {
+    /// "inputs", "model", "output_folder", or "disk_space"
+    category: &'static str,
+    passed: bool,
+    message: String,
+}
+
+/// Result of `preflight_run`; `ready` is set when every check passed
+#[derive(Serialize)]
+struct PreflightReport {
+    ready: bool,
+    checks: Vec<PreflightCheck>,
+}
+
+// Rough size of a run's output relative to its input audio, with a floor for
+// small runs; predictions are far smaller than the audio they describe
+const PREFLIGHT_OUTPUT_FRACTION: u64 = 10;
+const PREFLIGHT_MIN_OUTPUT_BYTES: u64 = 50 * 1024 * 1024;
+
+// Unreadable inputs named in the preflight message before the rest are counted
+const PREFLIGHT_LISTED_FILES: usize = 3;
+
+/// Check everything a run needs before it is submitted: the input files are
+/// readable, the model file looks valid, the output folder is writable, and its disk
+/// has room for the estimated output (a tenth of the input size, at least 50 MB).
+/// Each check is reported separately so the UI can show a checklist.
+#[tauri::command]
+async fn preflight_run(input_paths: Vec<String>, model_path: String, output_dir: String) -> Result<PreflightReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut checks = Vec::new();
+
+        let unreadable: Vec<&String> = input_paths.iter().filter(|path| !is_readable_file(path)).collect();
+        let input_bytes: u64 = input_paths.iter()
+            .filter_map(|path| fs::metadata(path).ok())
+            .map(|meta| meta.len())
+            .sum();
+        checks.push(PreflightCheck {
+            category: "inputs",
+            passed: !input_paths.is_empty() && unreadable.is_empty(),
+            message: if input_paths.is_empty() {
+                "No audio files selected".to_string()
+            } else if unreadable.is_empty() {
+                format!("{} audio files readable", input_paths.len())
+            } else {
+                let mut names: Vec<String> = unreadable.iter().take(PREFLIGHT_LISTED_FILES).map(|p| p.to_string()).collect();
+                if unreadable.len() > PREFLIGHT_LISTED_FILES {
+                    names.push(format!("and {} more", unreadable.len() - PREFLIGHT_LISTED_FILES));
+                }
+                format!("{} of {} files can't be read: {}", unreadable.len(), input_paths.len(), names.join(", "))
+            },
+        });
+
+        let model = check_model_file(model_path);
+        checks.push(PreflightCheck {
+            category: "model",
+            passed: model.looks_valid,
+            message: match (&model.error, model.detected_format) {
+                (Some(error), _) => error.clone(),
+                (None, Some(format)) if model.looks_valid => format!("Model file looks like a {} model", format),
+                (None, _) => "Model file doesn't look like a supported model format".to_string(),
+            },
+        });
+
+        let folder = check_output_folder(output_dir);
+        checks.push(PreflightCheck {
+            category: "output_folder",
+            passed: folder.writable,
+            message: folder.message.unwrap_or_else(|| "Output folder is writable".to_string()),
+        });
+
+        let needed = (input_bytes / PREFLIGHT_OUTPUT_FRACTION).max(PREFLIGHT_MIN_OUTPUT_BYTES);
+        let existing = Path::new(&folder.path).ancestors().find(|p| p.exists());
+        let available = existing.and_then(system::disk_space).map(|(available, _)| available);
+        checks.push(match available {
+            Some(available) => PreflightCheck {
+                category: "disk_space",
+                passed: available >= needed,
+                message: format!(
+                    "{} MB free, about {} MB needed",
+                    available / (1024 * 1024),
+                    needed.div_ceil(1024 * 1024)
+                ),
+            },
+            None => PreflightCheck {
+                category: "disk_space",
+                passed: false,
+                message: "Failed to determine free disk space for the output folder".to_string(),
+            },
+        });
+
+        PreflightReport { ready: checks.iter().all(|check| check.passed), checks }
+    })
+    .await
+    .map_err(|e| format!("Preflight check failed: {}", e))
+}
+
+const SCAN_CHECKPOINT_MAX_AGE_KEY: &str = "scan_checkpoint_max_age_hours";
+const DEFAULT_SCAN_CHECKPOINT_MAX_AGE_HOURS: u64 = 24;
+
+fn scan_checkpoint_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("scan_checkpoints"))
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))
+}
+
+/// Checkpoint file for scans of `folder`, named by a hash of its path
+fn scan_checkpoint_path(app: &tauri::AppHandle, folder: &str) -> Result<PathBuf, String> {
+    let key = blake3::hash(folder.as_bytes()).to_hex();
+    Ok(scan_checkpoint_dir(app)?.join(format!("{}.json", &key[..16])))
+}
+
+/// Whether a checkpoint file is older than the configured maximum age
+fn scan_checkpoint_is_stale(app: &tauri::AppHandle, path: &Path) -> bool {
+    let hours = settings::get::<u64>(app, SCAN_CHECKPOINT_MAX_AGE_KEY)
+        .unwrap_or(DEFAULT_SCAN_CHECKPOINT_MAX_AGE_HOURS);
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_none_or(|age| age > Duration::from_secs(hours.saturating_mul(3600)))
+}
+
+/// Remove checkpoints past the maximum age (best-effort)
+fn discard_stale_scan_checkpoints(app: &tauri::AppHandle) {
+    let Ok(entries) = scan_checkpoint_dir(app).and_then(|dir| fs::read_dir(dir).map_err(|e| e.to_string())) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if scan_checkpoint_is_stale(app, &entry.path()) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// Run a scan as a "folder_scan" job, saving its progress for `resume_scan` as it
+/// goes. `job-progress` counts folders read, with a total of 0 since it isn't known
+/// up front. The checkpoint is kept if a folder could not be read or the job was
+/// cancelled, and removed otherwise, since resuming a finished or failed scan has
+/// nothing to gain; a cancelled scan returns the files found so far.
+fn run_checkpointed_scan(
+    app: &tauri::AppHandle,
+    job: &jobs::Job,
+    folder: &str,
+    checkpoint: scan::ScanCheckpoint,
+) -> Result<Vec<String>, String> {
+    let checkpoint_path = scan_checkpoint_path(app, folder)?;
+    // Whether the checkpoint on disk matches the last one handed to `save`
+    let saved = std::cell::Cell::new(false);
+    let save = |checkpoint: &scan::ScanCheckpoint| {
+        let result = serde_json::to_vec(checkpoint)
+            .map_err(|e| e.to_string())
+            .and_then(|json| {
+                fs::create_dir_all(checkpoint_path.parent().unwrap_or(Path::new(".")))
+                    .and_then(|_| write_atomically(&checkpoint_path, &json))
+                    .map_err(|e| e.to_string())
+            });
+        saved.set(result.is_ok());
+        if let Err(e) = result {
+            app_eprintln!("Failed to save scan checkpoint: {}", e);
+        }
+    };
+    let result = scan::resume(checkpoint, save, || {
+        job.advance(app);
+        !job.is_cancelled()
+    });
+    job.finish(app);
+    match result {
+        Ok(files) => {
+            if !job.is_cancelled() {
+                let _ = fs::remove_file(&checkpoint_path);
+            }
+            Ok(files.iter().map(|p| p.to_string_lossy().to_string()).collect())
+        }
+        Err(scan::ScanError::Unreadable(e)) if saved.get() => {
+            Err(format!("{} (progress saved; use resume_scan to continue)", e))
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&checkpoint_path);
+            Err(e.to_string())
+        }
+    }
+}
+
+/// Return every audio file in a folder (optionally recursing into subfolders),
+/// sorted for deterministic ordering. Symlinks are skipped unless
+/// `resolve_symlinks` is set, in which case they are reported by their targets.
+/// Progress is checkpointed in the app data dir, so a scan cut short by an
+/// unreadable folder (e.g. a dropped network mount) or by `cancel_job` can be
+/// picked up with `resume_scan`.
+#[tauri::command]
+async fn scan_folder_for_audio(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, jobs::JobState>,
+    folder: String,
+    recursive: bool,
+    resolve_symlinks: Option<bool>,
+) -> Result<Vec<String>, String> {
+    let root = PathBuf::from(&folder);
+    if !root.is_dir() {
+        return Err(format!("Folder does not exist: {}", folder));
+    }
+    let job = jobs.start(&app, "folder_scan", 0);
+    tauri::async_runtime::spawn_blocking(move || {
+        discard_stale_scan_checkpoints(&app);
+        match scan::ScanCheckpoint::new(&root, recursive, resolve_symlinks.unwrap_or(false)) {
+            Ok(checkpoint) => run_checkpointed_scan(&app, &job, &folder, checkpoint),
+            Err(e) => {
+                job.finish(&app);
+                Err(e)
+            }
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to scan folder: {}", e))?
+}
+
+/// The saved checkpoint for `folder`, unless it is missing or past its maximum age
+fn load_scan_checkpoint(app: &tauri::AppHandle, folder: &str) -> Result<scan::ScanCheckpoint, String> {
+    let checkpoint_path = scan_checkpoint_path(app, folder)?;
+    if !checkpoint_path.exists() {
+        return Err(format!("No interrupted scan of {} to resume", folder));
+    }
+    if scan_checkpoint_is_stale(app, &checkpoint_path) {
+        let _ = fs::remove_file(&checkpoint_path);
+        return Err(format!("The saved scan of {} was too old and has been discarded; start a new scan", folder));
+    }
+    fs::read(&checkpoint_path)
+        .map_err(|e| format!("Failed to read scan checkpoint: {}", e))
+        .and_then(|json| serde_json::from_slice(&json).map_err(|e| format!("Invalid scan checkpoint: {}", e)))
+}
+
+/// Continue an interrupted `scan_folder_for_audio` of `folder` from its last
+/// checkpoint, with the options it was started with. Checkpoints older than the
+/// `scan_checkpoint_max_age_hours` setting (24 by default) are discarded, since the
+/// folder may have changed since.
+#[tauri::command]
+async fn resume_scan(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, jobs::JobState>,
+    folder: String,
+) -> Result<Vec<String>, String> {
+    let job = jobs.start(&app, "folder_scan", 0);
+    tauri::async_runtime::spawn_blocking(move || match load_scan_checkpoint(&app, &folder) {
+        Ok(checkpoint) => run_checkpointed_scan(&app, &job, &folder, checkpoint),
+        Err(e) => {
+            job.finish(&app);
+            Err(e)
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to resume scan: {}", e))?
+}
+
+/// Header information for one file, as returned by `get_audio_info`
+#[derive(Serialize)]
+struct AudioInfo {
+    path: String,
+    duration_seconds: Option<f64>,
+    sample_rate: Option<u32>,
+    channels: Option<u16>,
+    format: Option<String>,
+    /// Why the file could not be read; the other fields are empty when set
+    error: Option<String>,
+}
+
+/// Read duration, sample rate, channel count and codec from each file's headers.
+/// Files are probed in parallel as a "scan" job, with `job-progress` and
+/// `scan-progress` after each one, and unreadable files are reported individually
+/// instead of failing the batch. A scan stopped with `cancel_job` or `cancel_scan`
+/// returns the files probed so far, in input order.
+#[tauri::command]
+async fn get_audio_info(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, jobs::JobState>,
+    paths: Vec<String>,
+) -> Result<Vec<AudioInfo>, String> {
+    let total = paths.len();
+    let job = jobs.start(&app, "scan", total);
+    let workers = io_concurrency(&app);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(total));
+
+        thread::scope(|scope| {
+            for _ in 0..workers.min(total.max(1)) {
+                scope.spawn(|| loop {
+                    if job.is_cancelled() {
+                        break;
+                    }
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(path) = paths.get(i) else {
+                        break;
+                    };
+
+                    let info = match audio::probe(Path::new(path)) {
+                        Ok(props) => AudioInfo {
+                            path: path.clone(),
+                            duration_seconds: props.duration_seconds,
+                            sample_rate: Some(props.sample_rate),
+                            channels: Some(props.channels),
+                            format: Some(props.format),
+                            error: None,
+                        },
+                        Err(e) => AudioInfo {
+                            path: path.clone(),
+                            duration_seconds: None,
+                            sample_rate: None,
+                            channels: None,
+                            format: None,
+                            error: Some(e),
+                        },
+                    };
+                    results.lock().unwrap().push((i, info));
+                    let done = job.advance(&app);
+                    let _ = app.emit("scan-progress", Progress { done, total });
+                });
+            }
+        });
+        job.finish(&app);
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|(i, _)| *i);
+        results.into_iter().map(|(_, info)| info).collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to read audio info: {}", e))
+}
+
+/// Cancel the running `get_audio_info` scans; same as `cancel_job` on their ids
+#[tauri::command]
+async fn cancel_scan(jobs: tauri::State<'_, jobs::JobState>) -> Result<(), String> {
+    jobs.cancel_kind("scan");
+    Ok(())
+}
+
+/// Ask a running job to stop; it returns whatever it finished so far
+#[tauri::command]
+async fn cancel_job(jobs: tauri::State<'_, jobs::JobState>, job_id: u64) -> Result<(), String> {
+    let job = jobs.get(job_id).ok_or_else(|| format!("Unknown job: {}", job_id))?;
+    job.cancel();
+    Ok(())
+}
+
+/// Progress of a running or recently finished job
+#[tauri::command]
+async fn job_status(jobs: tauri::State<'_, jobs::JobState>, job_id: u64) -> Result<jobs::JobStatus, String> {
+    jobs.get(job_id)
+        .map(|job| job.status())
+        .ok_or_else(|| format!("Unknown job: {}", job_id))
+}
+
+/// Totals for the audio files under a folder, as returned by `summarize_folder`
+#[derive(Default, Serialize)]
+struct FolderSummary {
+    /// Audio files found, including unreadable ones
+    file_count: usize,
+    total_duration_seconds: f64,
+    total_bytes: u64,
+    /// Files whose headers could not be read; their size still counts
+    unreadable_count: usize,
+}
+
+/// Count the audio files in a folder and add up their size and duration.
+/// Durations come from headers only, probed in parallel; files whose headers
+/// don't report a length add nothing to the total.
+#[tauri::command]
+async fn summarize_folder(app: tauri::AppHandle, folder: String, recursive: bool) -> Result<FolderSummary, String> {
+    let root = PathBuf::from(&folder);
+    if !root.is_dir() {
+        return Err(format!("Folder does not exist: {}", folder));
+    }
+    let workers = io_concurrency(&app);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let files = scan::collect_audio_files(&root, recursive, false)?;
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let summary = Mutex::new(FolderSummary { file_count: files.len(), ..Default::default() });
+
+        thread::scope(|scope| {
+            for _ in 0..workers.min(files.len().max(1)) {
+                scope.spawn(|| {
+                    while let Some(path) = files.get(next.fetch_add(1, Ordering::SeqCst)) {
+                        let bytes = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+                        let duration = audio::probe(path).map(|props| props.duration_seconds.unwrap_or(0.0));
+
+                        let mut summary = summary.lock().unwrap();
+                        summary.total_bytes += bytes;
+                        match duration {
+                            Ok(seconds) => summary.total_duration_seconds += seconds,
+                            Err(_) => summary.unreadable_count += 1,
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(summary.into_inner().unwrap())
+    })
+    .await
+    .map_err(|e| format!("Failed to summarize folder: {}", e))?
+}
+
+/// Result of `canonicalize_paths`
+#[derive(Serialize)]
+struct CanonicalPaths {
+    paths: Vec<String>,
+    /// Inputs that could not be resolved (missing files, broken symlinks)
+    dropped: usize,
+}
+
+/// Resolve each path to its canonical form and drop repeats, keeping the first
+/// occurrence, so a file picked both by folder scan and by hand (or through a
+/// symlink or `./`) is only processed once
+#[tauri::command]
+async fn canonicalize_paths(paths: Vec<String>) -> Result<CanonicalPaths, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut seen = std::collections::HashSet::new();
+        let mut dropped = 0;
+        let paths = paths.iter()
+            .filter_map(|path| match fs::canonicalize(path) {
+                Ok(resolved) => Some(resolved.to_string_lossy().to_string()),
+                Err(_) => {
+                    dropped += 1;
+                    None
+                }
+            })
+            .filter(|path| seen.insert(path.clone()))
+            .collect();
+        CanonicalPaths { paths, dropped }
+    })
+    .await
+    .map_err(|e| format!("Failed to resolve paths: {}", e))
+}
+
+/// Each path relative to `base` for display, or unchanged when it isn't under `base`
+#[tauri::command]
+async fn relativize_paths(paths: Vec<String>, base: String) -> Result<Vec<String>, String> {
+    if base.trim().is_empty() {
+        return Err("Base folder is empty".to_string());
+    }
+    let base = Path::new(&base);
+    Ok(paths.into_iter()
+        .map(|path| match Path::new(&path).strip_prefix(base) {
+            Ok(relative) if !relative.as_os_str().is_empty() => relative.to_string_lossy().to_string(),
+            _ => path,
+        })
+        .collect())
+}
+
+/// Split `paths` into batches of `batch_size` for submitting to the backend.
+/// With `by_duration` the same number of batches is made, but cut so each holds a
+/// similar total duration (read from headers; unreadable files count as zero).
+/// Input order is kept either way.
+#[tauri::command]
+async fn batch_paths(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    batch_size: usize,
+    by_duration: Option<bool>,
+) -> Result<Vec<Vec<String>>, String> {
+    if batch_size == 0 {
+        return Err("Batch size must be at least 1".to_string());
+    }
+    if !by_duration.unwrap_or(false) {
+        return Ok(paths.chunks(batch_size).map(<[String]>::to_vec).collect());
+    }
+    let workers = io_concurrency(&app);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let durations = Mutex::new(vec![0.0; paths.len()]);
+        thread::scope(|scope| {
+            for _ in 0..workers.min(paths.len().max(1)) {
+                scope.spawn(|| {
+                    let claim = || {
+                        let i = next.fetch_add(1, Ordering::SeqCst);
+                        paths.get(i).map(|path| (i, path))
+                    };
+                    while let Some((i, path)) = claim() {
+                        let seconds = audio::probe(Path::new(path))
+                            .ok()
+                            .and_then(|props| props.duration_seconds)
+                            .unwrap_or(0.0);
+                        durations.lock().unwrap()[i] = seconds;
+                    }
+                });
+            }
+        });
+        let durations = durations.into_inner().unwrap();
+
+        let batch_count = paths.len().div_ceil(batch_size);
+        let target = durations.iter().sum::<f64>() / batch_count.max(1) as f64;
+        if target <= 0.0 {
+            // No durations to balance by
+            return paths.chunks(batch_size).map(<[String]>::to_vec).collect();
+        }
+        let mut batches: Vec<Vec<String>> = Vec::with_capacity(batch_count);
+        let mut current = Vec::new();
+        let mut elapsed = 0.0;
+        for (path, seconds) in paths.into_iter().zip(durations) {
+            current.push(path);
+            elapsed += seconds;
+            // Cut where the running total crosses the next multiple of the target
+            if batches.len() + 1 < batch_count && elapsed >= target * (batches.len() + 1) as f64 {
+                batches.push(std::mem::take(&mut current));
+            }
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
+        batches
+    })
+    .await
+    .map_err(|e| format!("Failed to batch paths: {}", e))
+}
+
+/// One entry returned by `list_directory`
+#[derive(Serialize)]
+struct DirEntry {
+    name: String,
+    path: String,
+    is_dir: bool,
+    size: u64,
+    /// Last modification time in seconds since the unix epoch
+    modified: Option<u64>,
+}
+
+/// List a folder's contents, folders first, each group sorted by name.
+/// Entries whose metadata can't be read (broken links, permission errors) are skipped.
+#[tauri::command]
+async fn list_directory(path: String) -> Result<Vec<DirEntry>, String> {
+    let entries = fs::read_dir(&path)
+        .map_err(|e| format!("Failed to read folder: {}", e))?;
+
+    let mut listing: Vec<DirEntry> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = fs::metadata(entry.path()).ok()?;
+            let modified = meta.modified().ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|age| age.as_secs());
+            Some(DirEntry {
+                name: entry.file_name().to_string_lossy().to_string(),
+                path: entry.path().to_string_lossy().to_string(),
+                is_dir: meta.is_dir(),
+                size: meta.len(),
+                modified,
+            })
+        })
+        .collect();
+
+    listing.sort_by(|a, b| {
+        b.is_dir.cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(listing)
+}
+
+/// Digest of one file from `hash_files`
+#[derive(Serialize)]
+struct FileHash {
+    path: String,
+    /// Lowercase hex digest
+    hash: Option<String>,
+    error: Option<String>,
+}
+
+/// Incremental hasher for the algorithms `hash_files` supports
+enum FileHasher {
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl FileHasher {
+    fn new(algorithm: &str) -> Option<Self> {
+        use sha2::Digest;
+        match algorithm {
+            "sha256" => Some(FileHasher::Sha256(sha2::Sha256::new())),
+            "blake3" => Some(FileHasher::Blake3(Box::new(blake3::Hasher::new()))),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        use sha2::Digest;
+        match self {
+            FileHasher::Sha256(hasher) => hasher.update(data),
+            FileHasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        use sha2::Digest;
+        match self {
+            FileHasher::Sha256(hasher) => hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect(),
+            FileHasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+        }
+    }
+}
+
+/// Stream a file through the hasher in fixed-size chunks
+fn hash_file(path: &str, mut hasher: FileHasher) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut buffer = vec![0u8; 1 << 16];
+    loop {
+        let n = file.read(&mut buffer)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+    Ok(hasher.finalize_hex())
+}
+
+/// Hash each file with `sha256` or `blake3` for provenance records, as a "hash" job
+/// with `job-progress` after each file. Unreadable files are reported individually
+/// instead of failing the batch; a cancelled job returns the hashes done so far.
+#[tauri::command]
+async fn hash_files(
+    app: tauri::AppHandle,
+    jobs: tauri::State<'_, jobs::JobState>,
+    paths: Vec<String>,
+    algorithm: String,
+) -> Result<Vec<FileHash>, String> {
+    let algorithm = algorithm.to_lowercase();
+    if FileHasher::new(&algorithm).is_none() {
+        return Err(format!("Unsupported hash algorithm: {} (expected sha256 or blake3)", algorithm));
+    }
+    let job = jobs.start(&app, "hash", paths.len());
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let hashes = paths.into_iter()
+            .take_while(|_| !job.is_cancelled())
+            .map(|path| {
+                let result = match hash_file(&path, FileHasher::new(&algorithm).expect("validated above")) {
+                    Ok(hash) => FileHash { path, hash: Some(hash), error: None },
+                    Err(e) => FileHash { path, hash: None, error: Some(e) },
+                };
+                job.advance(&app);
+                result
+            })
+            .collect();
+        job.finish(&app);
+        hashes
+    })
+    .await
+    .map_err(|e| format!("Failed to hash files: {}", e))
+}
+
+/// Files with identical contents, from `find_duplicate_audio`
+#[derive(Serialize)]
+struct DuplicateGroup {
+    /// blake3 of the full contents
+    hash: String,
+    size_bytes: u64,
+    paths: Vec<String>,
+}
+
+// Bytes read from each end of a file for the `find_duplicate_audio` pre-filter
+const DUPLICATE_SAMPLE_BYTES: u64 = 1 << 20;
+
+/// Hash of the size and the first and last `DUPLICATE_SAMPLE_BYTES` of a file, and
+/// the blake3 of its full contents when it is small enough (at most twice that
+/// size) for the sample to cover every byte
+fn sample_hash(path: &str, size: u64) -> Result<(String, Option<String>), String> {
+    use std::io::{Seek, SeekFrom};
+
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut buffer = Vec::new();
+    (&mut file).take(DUPLICATE_SAMPLE_BYTES).read_to_end(&mut buffer)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let tail_start = size.saturating_sub(DUPLICATE_SAMPLE_BYTES).max(DUPLICATE_SAMPLE_BYTES);
+    if tail_start < size {
+        file.seek(SeekFrom::Start(tail_start))
+            .and_then(|_| file.read_to_end(&mut buffer))
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(&size.to_le_bytes());
+    hasher.update(&buffer);
+    let contents = (size <= 2 * DUPLICATE_SAMPLE_BYTES).then(|| blake3::hash(&buffer).to_hex().to_string());
+    Ok((hasher.finalize().to_hex().to_string(), contents))
+}
+
+/// Group `paths` whose contents are identical, returning only groups with more than
+/// one member. Files are compared by size first, then by a hash of their first and
+/// last megabyte, and only files still matching are hashed in full. Unreadable
+/// files are left out.
+#[tauri::command]
+async fn find_duplicate_audio(paths: Vec<String>) -> Result<Vec<DuplicateGroup>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut by_size: HashMap<u64, Vec<String>> = HashMap::new();
+        for path in paths {
+            if let Some(meta) = fs::metadata(&path).ok().filter(|meta| meta.is_file()) {
+                by_size.entry(meta.len()).or_default().push(path);
+            }
+        }
+
+        let mut groups = Vec::new();
+        for (size, candidates) in by_size.into_iter().filter(|(_, paths)| paths.len() > 1) {
+            let mut by_sample: HashMap<(String, Option<String>), Vec<String>> = HashMap::new();
+            for path in candidates {
+                if let Ok(hashes) = sample_hash(&path, size) {
+                    by_sample.entry(hashes).or_default().push(path);
+                }
+            }
+            for ((_, contents), candidates) in by_sample.into_iter().filter(|(_, paths)| paths.len() > 1) {
+                // Small enough that the sample already covered every byte
+                if let Some(hash) = contents {
+                    groups.push(DuplicateGroup { hash, size_bytes: size, paths: candidates });
+                    continue;
+                }
+                let mut by_hash: HashMap<String, Vec<String>> = HashMap::new();
+                for path in candidates {
+                    if let Ok(hash) = hash_file(&path, FileHasher::new("blake3").expect("supported algorithm")) {
+                        by_hash.entry(hash).or_default().push(path);
+                    }
+                }
+                groups.extend(by_hash.into_iter()
+                    .filter(|(_, paths)| paths.len() > 1)
+                    .map(|(hash, paths)| DuplicateGroup { hash, size_bytes: size, paths }));
+            }
+        }
+        groups.sort_by(|a, b| a.paths[0].cmp(&b.paths[0]));
+        groups
+    })
+    .await
+    .map_err(|e| format!("Failed to find duplicates: {}", e))
+}
+
+/// Result of `resolve_link`
+#[derive(Serialize)]
+struct LinkInfo {
+    link: String,
+    target: String,
+    is_symlink: bool,
+}
+
+/// Report whether a path is a symlink and where it ultimately points
+#[tauri::command]
+async fn resolve_link(path: String) -> Result<LinkInfo, String> {
+    let meta = fs::symlink_metadata(&path)
+        .map_err(|e| format!("Failed to read path: {}", e))?;
+    let is_symlink = meta.file_type().is_symlink();
+    let target = fs::canonicalize(&path).map_err(|e| {
+        if is_symlink {
+            format!("Broken symlink {}: {}", path, e)
+        } else {
+            format!("Failed to resolve path: {}", e)
+        }
+    })?;
+    Ok(LinkInfo {
+        link: path,
+        target: target.to_string_lossy().to_string(),
+        is_symlink,
+    })
+}
+
+/// Convert every audio file in `folder` to a uniform WAV format, writing into a
+/// mirrored folder structure under `out_dir`. Files are processed in parallel
+/// and reported individually; a cancelled run returns the files finished so far.
+#[tauri::command]
+async fn normalize_audio_folder(
+    app: tauri::AppHandle,
+    normalize_state: tauri::State<'_, NormalizeState>,
+    folder: String,
+    out_dir: String,
+    target: audio::AudioTarget,
+    recursive: bool,
+) -> Result<NormalizeReport, String> {
+    target.validate()?;
+    let root = PathBuf::from(&folder);
+    if !root.is_dir() {
+        return Err(format!("Folder does not exist: {}", folder));
+    }
+    let out_root = PathBuf::from(&out_dir);
+    fs::create_dir_all(&out_root)
+        .map_err(|e| format!("Failed to create output folder: {}", e))?;
+
+    let cancel = normalize_state.cancel.clone();
+    cancel.store(false, Ordering::SeqCst);
+    let workers = io_concurrency(&app);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        // Skip anything already inside the output folder (e.g. a previous run's results)
+        let out_real = fs::canonicalize(&out_root).unwrap_or_else(|_| out_root.clone());
+        let inputs: Vec<PathBuf> = scan::collect_audio_files(&root, recursive, false)?
+            .into_iter()
+            .filter(|p| !p.starts_with(&out_real) && !p.starts_with(&out_root))
+            .collect();
+
+        let total = inputs.len();
+        let next = std::sync::atomic::AtomicUsize::new(0);
+        let done = std::sync::atomic::AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(total));
+
+        thread::scope(|scope| {
+            for _ in 0..workers.min(total.max(1)) {
+                scope.spawn(|| loop {
+                    if cancel.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(input) = inputs.get(i) else {
+                        break;
+                    };
+
+                    let relative = input.strip_prefix(&root).unwrap_or(input.as_path());
+                    let output = out_root.join(relative).with_extension("wav");
+                    let outcome = output.parent()
+                        .map(|parent| fs::create_dir_all(parent)
+                            .map_err(|e| format!("Failed to create output folder: {}", e)))
+                        .unwrap_or(Ok(()))
+                        .and_then(|_| audio::convert_file(input, &output, &target));
+
+                    results.lock().unwrap().push(NormalizeResult {
+                        input: input.to_string_lossy().to_string(),
+                        output: output.to_string_lossy().to_string(),
+                        error: outcome.err(),
+                    });
+                    let finished = done.fetch_add(1, Ordering::SeqCst) + 1;
+                    let _ = app.emit("normalize-progress", Progress { done: finished, total });
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by(|a, b| a.input.cmp(&b.input));
+        let failed = results.iter().filter(|r| r.error.is_some()).count();
+        Ok(NormalizeReport {
+            succeeded: results.len() - failed,
+            failed,
+            cancelled: cancel.load(Ordering::SeqCst),
+            results,
+        })
+    })
+    .await
+    .map_err(|e| format!("Normalize task failed: {}", e))?
+}
+
+/// Cancel the running `normalize_audio_folder` job
+#[tauri::command]
+async fn cancel_normalize(normalize_state: tauri::State<'_, NormalizeState>) -> Result<(), String> {
+    normalize_state.cancel.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Files that look like numbered parts of one continuous recording
+#[derive(Serialize, Deserialize)]
+struct RecordingGroup {
+    base_name: String,
+    /// Part paths in part-number order
+    parts: Vec<String>,
+    part_numbers: Vec<u32>,
+    sample_rate: u32,
+    channels: u16,
+    /// Part numbers absent between the first and last part
+    missing_parts: Vec<u32>,
+}
+
+/// Split a file stem like `SITE_20230715_001` into (`SITE_20230715`, 1).
+/// Longer digit runs are timestamps (`..._063000`), not part counters.
+fn split_part_number(stem: &str) -> Option<(String, u32)> {
+    const MAX_PART_DIGITS: usize = 4;
+
+    let digits = stem.len() - stem.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 || digits > MAX_PART_DIGITS || digits == stem.len() {
+        return None;
+    }
+    let (base, number) = stem.split_at(stem.len() - digits);
+    let base = base.trim_end_matches(['_', '-', ' ', '.']);
+    if base.is_empty() {
+        return None;
+    }
+    Some((base.to_string(), number.parse().ok()?))
+}
+
+/// Cluster files into split-recording groups by folder, base name, extension,
+/// and audio format. Only groups with more than one part are returned.
+#[tauri::command]
+async fn detect_recording_groups(paths: Vec<String>) -> Result<Vec<RecordingGroup>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        type GroupKey = (PathBuf, String, String, u32, u16);
+        let mut groups: BTreeMap<GroupKey, Vec<(u32, String)>> = BTreeMap::new();
+
+        for path_str in &paths {
+            let path = Path::new(path_str);
+            let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else {
+                continue;
+            };
+            let Some((base, part)) = split_part_number(&stem) else {
+                continue;
+            };
+            let Ok(props) = audio::probe(path) else {
+                continue;
+            };
+            let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+            let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            groups.entry((dir, base, ext, props.sample_rate, props.channels))
+                .or_default()
+                .push((part, path_str.clone()));
+        }
+
+        groups.into_iter()
+            .filter(|(_, parts)| parts.len() > 1)
+            .map(|((_, base_name, _, sample_rate, channels), mut parts)| {
+                parts.sort();
+                let part_numbers: Vec<u32> = parts.iter().map(|(n, _)| *n).collect();
+                let missing_parts = (part_numbers[0]..*part_numbers.last().unwrap())
+                    .filter(|n| part_numbers.binary_search(n).is_err())
+                    .collect();
+                RecordingGroup {
+                    base_name,
+                    parts: parts.into_iter().map(|(_, p)| p).collect(),
+                    part_numbers,
+                    sample_rate,
+                    channels,
+                    missing_parts,
+                }
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to detect recording groups: {}", e))
+}
+
+/// Concatenate a recording group's parts, in order, into a single WAV file
+#[tauri::command]
+async fn merge_recording_group(group: RecordingGroup, out_path: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let inputs: Vec<&Path> = group.parts.iter().map(Path::new).collect();
+        audio::concat_files(&inputs, Path::new(&out_path))?;
+        Ok(out_path)
+    })
+    .await
+    .map_err(|e| format!("Merge task failed: {}", e))?
+}
+
+/// Project file contents relevant to path portability
+#[derive(Deserialize)]
+struct ProjectFile {
+    /// Named roots, e.g. "deployments" -> "/Volumes/field_data/deployments"
+    #[serde(default)]
+    roots: BTreeMap<String, String>,
+}
+
+/// Load the named roots from a project JSON file
+fn load_project_roots(project: &str) -> Result<BTreeMap<String, String>, String> {
+    let content = fs::read_to_string(project)
+        .map_err(|e| format!("Failed to read project file: {}", e))?;
+    let parsed: ProjectFile = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse project file: {}", e))?;
+    Ok(parsed.roots)
+}
+
+/// Resolve an `@root/relative/path` into an absolute path using the project's roots.
+/// Paths without an `@` prefix are returned unchanged. A tagged path can't climb out
+/// of its root, so `..` components are rejected.
+#[tauri::command]
+async fn resolve_root_path(project: String, tagged_path: String) -> Result<String, String> {
+    let Some(tagged) = tagged_path.strip_prefix('@') else {
+        return Ok(tagged_path);
+    };
+
+    let (root_name, relative) = match tagged.find(['/', '\\']) {
+        Some(idx) => (&tagged[..idx], &tagged[idx + 1..]),
+        None => (tagged, ""),
+    };
+
+    let roots = load_project_roots(&project)?;
+    let root = roots.get(root_name)
+        .ok_or_else(|| format!("Unknown project root: @{}", root_name))?;
+
+    let mut resolved = PathBuf::from(root);
+    for component in relative.split(['/', '\\']).filter(|c| !c.is_empty() && *c != ".") {
+        if component == ".." {
+            return Err(format!("Tagged path escapes its root: {}", tagged_path));
+        }
+        resolved.push(component);
+    }
+    Ok(resolved.to_string_lossy().to_string())
+}
+
+/// Convert an absolute path into an `@root/relative/path` using the most specific
+/// matching project root. Paths outside every root are returned unchanged.
+#[tauri::command]
+async fn to_tagged_path(project: String, absolute_path: String) -> Result<String, String> {
+    let roots = load_project_roots(&project)?;
+    let path = Path::new(&absolute_path);
+
+    // Prefer the deepest root so nested roots win over their parents
+    let best = roots.iter()
+        .filter_map(|(name, root)| {
+            path.strip_prefix(root).ok().map(|rel| (name, Path::new(root).components().count(), rel))
+        })
+        .max_by_key(|(_, depth, _)| *depth);
+
+    match best {
+        Some((name, _, rel)) => {
+            let parts: Vec<String> = rel.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            if parts.is_empty() {
+                Ok(format!("@{}", name))
+            } else {
+                Ok(format!("@{}/{}", name, parts.join("/")))
+            }
+        }
+        None => Ok(absolute_path),
+    }
+}
+
+/// Audio encoding used when estimating extracted clip sizes
+#[derive(Deserialize)]
+struct OutputFormat {
+    sample_rate: u32,
+    bit_depth: u16,
+    channels: u16,
+}
+
+/// Estimate the total bytes a clip extraction would write: one uncompressed WAV clip
+/// per prediction row whose best score meets `threshold`, padded on both sides
+#[tauri::command]
+async fn estimate_clips_size(
+    csv_path: String,
+    threshold: f32,
+    padding_secs: f64,
+    output_format: OutputFormat,
+) -> Result<u64, String> {
+    // Canonical WAV header size
+    const WAV_HEADER_BYTES: u64 = 44;
+
+    let bytes_per_second = output_format.sample_rate as f64
+        * output_format.channels as f64
+        * (output_format.bit_depth as f64 / 8.0);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut total: u64 = 0;
+        for row in predictions::open(Path::new(&csv_path))? {
+            let row = row?;
+            if row.max_score() >= threshold {
+                let duration = (row.end_time - row.start_time).max(0.0) + 2.0 * padding_secs.max(0.0);
+                total += WAV_HEADER_BYTES + (duration * bytes_per_second).ceil() as u64;
+            }
+        }
+        Ok(total)
+    })
+    .await
+    .map_err(|e| format!("Failed to estimate clip sizes: {}", e))?
+}
+
+/// Count detections per time bin for one file in a predictions CSV, returning
+/// `[bin_start_secs, count]` pairs. Bins span the whole recording (from its
+/// headers when readable), so quiet stretches show up as zeros.
+#[tauri::command]
+async fn detection_rate_timeline(
+    predictions_csv: String,
+    file: String,
+    bin_secs: f64,
+    threshold: f32,
+) -> Result<Vec<[f64; 2]>, String> {
+    if bin_secs <= 0.0 || !bin_secs.is_finite() {
+        return Err("Bin size must be a positive number of seconds".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let target = Path::new(&file);
+        let mut detection_starts = Vec::new();
+        let mut last_end: f64 = 0.0;
+        for row in predictions::open(Path::new(&predictions_csv))? {
+            let row = row?;
+            if Path::new(&row.file) != target {
+                continue;
+            }
+            last_end = last_end.max(row.end_time);
+            if row.max_score() >= threshold {
+                detection_starts.push(row.start_time);
+            }
+        }
+
+        let duration = audio::probe(target).ok()
+            .and_then(|props| props.duration_seconds)
+            .unwrap_or(last_end);
+        let bin_count = ((duration / bin_secs).ceil() as usize).max(1);
+
+        let mut counts = vec![0usize; bin_count];
+        for start in detection_starts {
+            let bin = ((start.max(0.0) / bin_secs) as usize).min(bin_count - 1);
+            counts[bin] += 1;
+        }
+
+        Ok(counts.into_iter()
+            .enumerate()
+            .map(|(i, count)| [i as f64 * bin_secs, count as f64])
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Failed to build detection timeline: {}", e))?
+}
+
+/// Read GUANO metadata fields from a WAV file (empty when the file has none)
+#[tauri::command]
+async fn read_guano(path: String) -> Result<BTreeMap<String, String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        audio::read_guano(Path::new(&path)).map(|guano| guano.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| format!("Failed to read GUANO metadata: {}", e))?
+}
+
+/// Result of `export_detections_geojson`
+#[derive(Serialize)]
+struct ExportReport {
+    out_path: String,
+    features_written: usize,
+    detections_without_gps: usize,
+    files_without_gps: Vec<String>,
+}
+
+/// Export detections as a GeoJSON FeatureCollection of points, placing each
+/// detection at its source file's GUANO `Loc Position`. Each row contributes its
+/// top-scoring class; rows below `threshold` (when given) are skipped.
+#[tauri::command]
+async fn export_detections_geojson(
+    predictions_csv: String,
+    audio_root: String,
+    out_path: String,
+    threshold: Option<f32>,
+) -> Result<ExportReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut rows = predictions::open(Path::new(&predictions_csv))?;
+        let classes = rows.classes.clone();
+
+        let mut positions: HashMap<String, Option<(f64, f64)>> = HashMap::new();
+        let mut features = Vec::new();
+        let mut detections_without_gps = 0;
+        let mut files_without_gps = Vec::new();
+
+        for row in &mut rows {
+            let row = row?;
+            let Some((class_idx, score)) = row.top_class() else {
+                continue;
+            };
+            if threshold.is_some_and(|t| score < t) {
+                continue;
+            }
+
+            // Prediction files may store paths relative to the audio root
+            let position = *positions.entry(row.file.clone()).or_insert_with(|| {
+                let path = Path::new(&audio_root).join(&row.file);
+                audio::read_guano(&path).ok().flatten().as_ref().and_then(audio::guano_position)
+            });
+            let Some((lat, lon)) = position else {
+                detections_without_gps += 1;
+                if !files_without_gps.contains(&row.file) {
+                    files_without_gps.push(row.file.clone());
+                }
+                continue;
+            };
+
+            features.push(serde_json::json!({
+                "type": "Feature",
+                "geometry": { "type": "Point", "coordinates": [lon, lat] },
+                "properties": {
+                    "file": row.file,
+                    "class": classes[class_idx],
+                    "score": score,
+                    "start_time": row.start_time,
+                    "end_time": row.end_time,
+                }
+            }));
+        }
+
+        let features_written = features.len();
+        let collection = serde_json::json!({ "type": "FeatureCollection", "features": features });
+        let content = serde_json::to_string(&collection)
+            .map_err(|e| format!("Failed to serialize GeoJSON: {}", e))?;
+        write_atomically(Path::new(&out_path), content.as_bytes())
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+
+        Ok(ExportReport { out_path, features_written, detections_without_gps, files_without_gps })
+    })
+    .await
+    .map_err(|e| format!("Failed to export GeoJSON: {}", e))?
+}
+
+/// Watch a folder for changes, emitting `folder-changed` per file or a single
+/// `folder-bulk-changed` summary while a burst of events is in progress, plus
+/// `new-audio-file` once each new recording has stopped growing
+#[tauri::command]
+async fn watch_folder(
+    app: tauri::AppHandle,
+    watches: tauri::State<'_, WatchState>,
+    folder: String,
+    burst: Option<watcher::BurstConfig>,
+) -> Result<(), String> {
+    if !Path::new(&folder).is_dir() {
+        return Err(format!("Folder does not exist: {}", folder));
+    }
+    let mut watchers = watches.watchers.lock().unwrap();
+    if watchers.contains_key(&folder) {
+        return Ok(());
+    }
+    let handle = watcher::start(app.clone(), &folder, burst.unwrap_or_default())?;
+    watchers.insert(folder, handle);
+    Ok(())
+}
+
+/// Stop watching a folder previously passed to `watch_folder`
+#[tauri::command]
+async fn unwatch_folder(watches: tauri::State<'_, WatchState>, folder: String) -> Result<(), String> {
+    watches.watchers.lock().unwrap()
+        .remove(&folder)
+        .map(|_| ())
+        .ok_or_else(|| format!("Folder is not being watched: {}", folder))
+}
+
+// Port probed for an already-running backend in dev mode
+const DEFAULT_BACKEND_PORT: u16 = 8000;
+
+// Environment variable that pins the backend to a specific port
+const BACKEND_PORT_ENV: &str = "DIPPER_SERVER_PORT";
+
+/// Port requested through `DIPPER_SERVER_PORT`, if set to a valid port number
+fn requested_backend_port() -> Option<u16> {
+    let value = std::env::var(BACKEND_PORT_ENV).ok()?;
+    match value.trim().parse::<u16>() {
+        Ok(port) if port != 0 => Some(port),
+        _ => {
+            app_eprintln!("  Warning: ignoring invalid {}={:?}, using defaults", BACKEND_PORT_ENV, value);
+            None
+        }
+    }
+}
+
+// Ports tried, in order, when starting our own backend
+const BACKEND_PORT_RANGE: std::ops::RangeInclusive<u16> = 8000..=8050;
+
+/// Find the first port in `BACKEND_PORT_RANGE` that nothing is listening on
+fn get_free_port() -> Option<u16> {
+    // A successful bind means the port is free; the listener is dropped right away
+    BACKEND_PORT_RANGE.into_iter()
+        .find(|&port| TcpListener::bind(("127.0.0.1", port)).is_ok())
+}
+
+// Server log lines shown on the splash when startup fails
+const STARTUP_FAILURE_LOG_LINES: usize = 8;
+
+/// Payload for `startup-failed`: why startup failed and the last few backend log lines
+#[derive(Clone, Serialize)]
+struct StartupFailure {
+    message: String,
+    logs: Vec<String>,
+}
+
+/// Put the splash into its error state, which offers Retry and Cancel, by emitting
+/// `startup-failed`. The failure is also kept for `get_startup_state`.
+fn show_startup_failure(app: &tauri::AppHandle, message: &str) {
+    let logs: Vec<String> = {
+        let log_state: tauri::State<ServerLogState> = app.state();
+        let lines = log_state.lines.lock().unwrap();
+        lines.iter()
+            .skip(lines.len().saturating_sub(STARTUP_FAILURE_LOG_LINES))
+            .map(|entry| entry.line.clone())
+            .collect()
+    };
+    let failure = StartupFailure { message: message.to_string(), logs };
+
+    let state: tauri::State<BackendState> = app.state();
+    *state.startup_failure.lock().unwrap() = Some(failure.clone());
+    let _ = app.emit("startup-failed", failure);
+}
+
+/// Payload for `startup-status`
+#[derive(Clone, Serialize)]
+struct StartupStatus {
+    /// "starting" while the sidecar is launched, "waiting" until its HTTP server
+    /// answers, then "ready"
+    stage: String,
+    /// 1-based health check attempt; 0 once the backend is ready
+    attempt: u32,
+    elapsed_secs: u64,
+    timeout_secs: u64,
+    message: String,
+}
+
+/// Emit `startup-status`, which the splash shows as its status line
+fn report_startup_status(app: &tauri::AppHandle, status: StartupStatus) {
+    let state: tauri::State<BackendState> = app.state();
+    *state.startup_status.lock().unwrap() = Some(status.clone());
+    let _ = app.emit("startup-status", status);
+}
+
+/// Startup progress and failure reported so far
+#[derive(Serialize)]
+struct StartupState {
+    status: Option<StartupStatus>,
+    failure: Option<StartupFailure>,
+}
+
+/// Current startup state, so the splash can catch up on events sent before it loaded
+#[tauri::command]
+async fn get_startup_state(state: tauri::State<'_, BackendState>) -> Result<StartupState, String> {
+    Ok(StartupState {
+        status: state.startup_status.lock().unwrap().clone(),
+        failure: state.startup_failure.lock().unwrap().clone(),
+    })
+}
+
+/// Who is listening on a local port, from `probe_server_identity`
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ServerIdentity {
+    /// A Dipper `lightweight_server`; older builds don't report a version
+    Dipper { version: Option<String> },
+    /// Something else accepts connections on the port
+    Other,
+    /// Nothing is listening
+    Free,
+}
+
+/// Identify the server on a local port by its /health signature. Anything that
+/// accepts a connection but doesn't answer like a Dipper backend is `Other`.
+fn identify_server(port: u16, timeout: Duration) -> ServerIdentity {
+    let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+    if TcpStream::connect_timeout(&addr, timeout).is_err() {
+        return ServerIdentity::Free;
+    }
+    let health = ureq::get(&health_url(port))
+        .timeout(timeout)
+        .call()
+        .ok()
+        .filter(|response| response.status() == 200)
+        .and_then(|response| response.into_json::<serde_json::Value>().ok());
+    match health {
+        Some(json) if is_dipper_health(&json) => ServerIdentity::Dipper {
+            version: json.get("version").and_then(|v| v.as_str()).map(str::to_string),
+        },
+        _ => ServerIdentity::Other,
+    }
+}
+
+/// Whether the server on `port` is Dipper's backend, some other service, or absent,
+/// so the UI never mistakes an unrelated service for the backend
+#[tauri::command]
+async fn probe_server_identity(port: u16) -> Result<ServerIdentity, String> {
+    tauri::async_runtime::spawn_blocking(move || identify_server(port, Duration::from_secs(3)))
+        .await
+        .map_err(|e| format!("Failed to probe port {}: {}", port, e))
+}
+
+/// Whether the backend we just spawned is ready for requests. Stricter than a TCP
+/// connect, which succeeds as soon as the socket is bound and before the HTTP stack
+/// answers. Any response, including an error status such as a 404 from an unrelated
+/// server, must carry the Dipper signature. There is deliberately no TCP-only fallback
+/// on 404: every backend release serves `/health` with that signature, so a 404 means
+/// something else holds the port.
+fn backend_accepts_requests(port: u16, timeout: Duration) -> bool {
+    match ureq::get(&health_url(port)).timeout(timeout).call() {
+        Ok(response) | Err(ureq::Error::Status(_, response)) => is_dipper_health_response(response),
+        Err(e) => {
+            app_println!("  ✗ Connection failed: {}", e);
+            false
+        }
+    }
+}
+
+fn health_url(port: u16) -> String {
+    format!("http://127.0.0.1:{}/health", port)
+}
+
+/// Whether a parsed /health body carries the Dipper backend's signature
+fn is_dipper_health(json: &serde_json::Value) -> bool {
+    json.get("status").and_then(|v| v.as_str()) == Some("ok")
+        && json.get("server_type").and_then(|v| v.as_str()) == Some("lightweight")
+}
+
+/// Whether a /health response carries the Dipper backend's signature
+fn is_dipper_health_response(response: ureq::Response) -> bool {
+    let status_code = response.status();
+    app_println!("  Health check got HTTP {}", status_code);
+
+    if status_code != 200 {
+        app_println!("  ✗ Unexpected status code: {}", status_code);
+        return false;
+    }
+
+    // Try to parse the JSON response
+    match response.into_string() {
+        Ok(body) => {
+            app_println!("  Health response body: {}", body);
+            match serde_json::from_str::<serde_json::Value>(&body) {
+                Ok(json) => {
+                    // Verify it's the Dipper backend by checking for expected fields
+                    if is_dipper_health(&json) {
+                        app_println!("  ✓ Valid Dipper backend detected!");
+                        return true;
+                    }
+                    app_println!("  ✗ Response doesn't match Dipper backend signature");
+                }
+                Err(e) => {
+                    app_println!("  ✗ Failed to parse JSON: {}", e);
+                }
+            }
+        }
+        Err(e) => {
+            app_println!("  ✗ Failed to read response body: {}", e);
+        }
+    }
+    false
+}
+
+// Environment variable overriding how long startup waits for the backend
+const STARTUP_TIMEOUT_ENV: &str = "DIPPER_STARTUP_TIMEOUT_SECS";
+const DEFAULT_STARTUP_TIMEOUT_SECS: u64 = 60;
+
+/// How `wait_for_server` polls the backend
+struct RetryPolicy {
+    /// Sleep after the first failed check; grows by half each retry
+    initial_interval: Duration,
+    max_interval: Duration,
+    /// Timeout for each individual health request
+    connect_timeout: Duration,
+    /// Give up once this much time has passed in total
+    deadline: Duration,
+}
+
+impl RetryPolicy {
+    fn with_deadline(deadline: Duration) -> Self {
+        RetryPolicy {
+            initial_interval: Duration::from_millis(250),
+            max_interval: Duration::from_secs(3),
+            connect_timeout: Duration::from_secs(2),
+            deadline,
+        }
+    }
+
+    /// Startup policy; the deadline comes from `DIPPER_STARTUP_TIMEOUT_SECS`
+    fn startup() -> Self {
+        let secs = std::env::var(STARTUP_TIMEOUT_ENV)
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|&secs| secs > 0)
+            .unwrap_or(DEFAULT_STARTUP_TIMEOUT_SECS);
+        Self::with_deadline(Duration::from_secs(secs))
+    }
+}
+
+/// Wait for the Dipper backend to be ready on the given port.
+/// `on_attempt` is called with the 1-based attempt number and the time waited so far
+/// after each failed check.
+fn wait_for_server(port: u16, policy: &RetryPolicy, on_attempt: impl Fn(u32, Duration)) -> bool {
+    let started = std::time::Instant::now();
+    let mut interval = policy.initial_interval;
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        if backend_accepts_requests(port, policy.connect_timeout) {
+            app_println!("✓ Dipper backend health check passed on port {}!", port);
+            return true;
+        }
+        let elapsed = started.elapsed();
+        if attempt <= 3 || attempt % 5 == 0 {
+            // Only print every 5th attempt after the first 3 to reduce spam
+            app_println!("⏳ Checking backend health on port {}... ({}s/{}s)", port, elapsed.as_secs(), policy.deadline.as_secs());
+        }
+        on_attempt(attempt, elapsed);
+        if elapsed >= policy.deadline {
+            break;
+        }
+        thread::sleep(interval.min(policy.deadline - elapsed));
+        interval = (interval * 3 / 2).min(policy.max_interval);
+    }
+    app_eprintln!("✗ Backend health check timed out after {}s ({} attempts)", policy.deadline.as_secs(), attempt);
+    false
+}
+
+/// Show the main window and close the splash, once; later calls do nothing.
+/// Either the frontend (`show_main_window`) or the startup wait may get here first.
+fn show_main_and_close_splash(app: &tauri::AppHandle) {
+    let state: tauri::State<BackendState> = app.state();
+    if state.main_shown.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if let Some(main_window) = app.get_webview_window("main") {
+        main_window.show().expect("Failed to show main window");
+    }
+    if let Some(splash_window) = app.get_webview_window("splash") {
+        splash_window.close().expect("Failed to close splash window");
+    }
+}
+
+/// Dismiss the splash early for flows that don't need the backend; startup keeps
+/// waiting for the server in the background
+#[tauri::command]
+async fn show_main_window(app: tauri::AppHandle) -> Result<(), String> {
+    show_main_and_close_splash(&app);
+    Ok(())
+}
+
+/// Wait for the backend on `port`, then swap the splash for the main window and keep
+/// supervising a sidecar we launched. On timeout the splash shows the error state.
+fn await_backend_ready(app_handle: tauri::AppHandle, port: u16) {
+    let policy = RetryPolicy::startup();
+    let timeout_secs = policy.deadline.as_secs();
+
+    app_println!("Waiting for backend server to be ready on port {}...", port);
+    let on_attempt = |attempt, elapsed: Duration| {
+        report_startup_status(&app_handle, StartupStatus {
+            stage: "waiting".to_string(),
+            attempt,
+            elapsed_secs: elapsed.as_secs(),
+            timeout_secs,
+            message: format!("Waiting for HTTP server... ({}s/{}s)", elapsed.as_secs(), timeout_secs),
+        });
+    };
+    if !wait_for_server(port, &policy, on_attempt) {
+        app_eprintln!("✗ Backend server health check timed out");
+        kill_unresponsive_sidecar(&app_handle, port);
+        show_startup_failure(&app_handle, "The backend server did not respond in time.");
+        return;
+    }
+
+    report_startup_status(&app_handle, StartupStatus {
+        stage: "ready".to_string(),
+        attempt: 0,
+        elapsed_secs: 0,
+        timeout_secs,
+        message: "Ready".to_string(),
+    });
+    app_println!("✓ Backend server is ready!");
+    let state: tauri::State<BackendState> = app_handle.state();
+    *state.capabilities.lock().unwrap() = Some(fetch_capabilities(port));
+    show_main_and_close_splash(&app_handle);
+
+    // Only a backend we launched ourselves is ours to restart
+    let launched = state.process.lock().unwrap().is_some();
+    if launched {
+        supervise_backend(app_handle.clone(), port);
+    }
+}
+
+/// Payload for `server-start-timeout`
+#[derive(Clone, Serialize)]
+struct ServerStartTimeout {
+    port: u16,
+    /// Everything the sidecar printed before it was killed
+    logs: Vec<ServerLogLine>,
+}
+
+/// Kill a sidecar that never answered within the startup deadline, so a hung
+/// process doesn't linger behind the error screen, and emit `server-start-timeout`
+/// with its output. A backend Dipper didn't launch is left alone.
+fn kill_unresponsive_sidecar(app: &tauri::AppHandle, port: u16) {
+    let state: tauri::State<BackendState> = app.state();
+    let Some(child) = state.process.lock().unwrap().take() else {
+        return;
+    };
+    app_eprintln!("  Killing unresponsive backend (pid {})", child.pid());
+    let _ = child.kill();
+
+    let log_state: tauri::State<ServerLogState> = app.state();
+    let logs = log_state.lines.lock().unwrap().iter().cloned().collect();
+    let _ = app.emit("server-start-timeout", ServerStartTimeout { port, logs });
+}
+
+/// Give up on startup: stop the sidecar if Dipper started it and quit. Called by the
+/// splash's Cancel button, so a hung backend never needs a force-quit.
+#[tauri::command]
+async fn cancel_startup(app: tauri::AppHandle, state: tauri::State<'_, BackendState>) -> Result<(), String> {
+    if state.main_shown.load(Ordering::SeqCst) {
+        return Err("Startup has already finished".to_string());
+    }
+    app_println!("Startup cancelled from the splash screen");
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || stop_backend_server(&handle))
+        .await
+        .map_err(|e| format!("Failed to stop backend: {}", e))?;
+    app.exit(0);
+    Ok(())
+}
+
+/// Start a fresh backend after a failed startup; called by the splash's Retry button
+#[tauri::command]
+async fn retry_backend_start(app: tauri::AppHandle, state: tauri::State<'_, BackendState>) -> Result<(), String> {
+    if state.startup_failure.lock().unwrap().take().is_none() {
+        return Err("Backend startup has not failed".to_string());
+    }
+    if let Some(child) = state.process.lock().unwrap().take() {
+        let _ = child.kill();
+    }
+
+    let Some(port) = requested_backend_port().or_else(get_free_port) else {
+        let message = format!(
+            "No free port between {} and {} for the backend server. Close other local servers and try again.",
+            BACKEND_PORT_RANGE.start(),
+            BACKEND_PORT_RANGE.end()
+        );
+        show_startup_failure(&app, &message);
+        return Err(message);
+    };
+
+    // Any progress event takes the splash out of its error state
+    report_startup_status(&app, StartupStatus {
+        stage: "starting".to_string(),
+        attempt: 0,
+        elapsed_secs: 0,
+        timeout_secs: RetryPolicy::startup().deadline.as_secs(),
+        message: "Restarting backend...".to_string(),
+    });
+    app_println!("→ Retrying backend startup on port {}", port);
+    let child = start_backend_server(&app, port);
+    *state.port.lock().unwrap() = Some(port);
+    *state.process.lock().unwrap() = child;
+
+    thread::spawn(move || await_backend_ready(app, port));
+    Ok(())
+}
+
+/// Snapshot of the backend for status indicators
+#[derive(Serialize)]
+struct ServerStatus {
+    running: bool,
+    port: Option<u16>,
+    /// Process id of the sidecar Dipper launched; `None` for an external dev backend
+    pid: Option<u32>,
+}
+
+/// Port and sidecar pid of the backend with `id`, from `list_servers`; the primary
+/// backend when `id` is `None`
+fn server_port_and_pid(
+    state: &BackendState,
+    registry: &ServerRegistry,
+    id: Option<u64>,
+) -> Result<(Option<u16>, Option<u32>), String> {
+    match id.unwrap_or(PRIMARY_SERVER_ID) {
+        PRIMARY_SERVER_ID => Ok((
+            *state.port.lock().unwrap(),
+            state.process.lock().unwrap().as_ref().map(|child| child.pid()),
+        )),
+        id => registry.servers.lock().unwrap().get(&id)
+            .map(|server| (Some(server.port), Some(server.child.pid())))
+            .ok_or_else(|| format!("No backend server with id {}", id)),
+    }
+}
+
+/// Whether a backend answers health checks: the one with `id` from `list_servers`,
+/// or the primary backend when `id` is omitted
+#[tauri::command]
+async fn is_server_running(
+    state: tauri::State<'_, BackendState>,
+    registry: tauri::State<'_, ServerRegistry>,
+    id: Option<u64>,
+) -> Result<bool, String> {
+    let (port, _) = server_port_and_pid(&state, &registry, id)?;
+    Ok(match port {
+        Some(port) => tauri::async_runtime::spawn_blocking(move || backend_is_healthy(port))
+            .await
+            .unwrap_or(false),
+        None => false,
+    })
+}
+
+/// Whether a TCP connection to `host:port` opens within `timeout_ms`, for pointing
+/// the app at a model server on another machine. An unresolvable host is an
+/// error; a refused or timed-out connection is just `false`.
+#[tauri::command]
+async fn check_remote_server(host: String, port: u16, timeout_ms: u64) -> Result<bool, String> {
+    if timeout_ms == 0 {
+        return Err("Timeout must be greater than zero".to_string());
+    }
+    let timeout = Duration::from_millis(timeout_ms);
+    tauri::async_runtime::spawn_blocking(move || {
+        let addrs: Vec<_> = (host.as_str(), port)
+            .to_socket_addrs()
+            .map_err(|e| format!("Invalid host {}: {}", host, e))?
+            .collect();
+        Ok(addrs.iter().any(|addr| TcpStream::connect_timeout(addr, timeout).is_ok()))
+    })
+    .await
+    .map_err(|e| format!("Connection check failed: {}", e))?
+}
+
+/// Health, port and sidecar process id of the backend
+#[tauri::command]
+async fn get_server_status(
+    state: tauri::State<'_, BackendState>,
+    registry: tauri::State<'_, ServerRegistry>,
+    id: Option<u64>,
+) -> Result<ServerStatus, String> {
+    let (port, pid) = server_port_and_pid(&state, &registry, id)?;
+    let running = match port {
+        Some(port) => tauri::async_runtime::spawn_blocking(move || backend_is_healthy(port))
+            .await
+            .unwrap_or(false),
+        None => false,
+    };
+    Ok(ServerStatus { running, port, pid })
+}
+
+/// Resource usage of the backend for a live readout
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum ServerStats {
+    Running {
+        pid: u32,
+        /// Summed over the sidecar's process tree; may exceed 100 on multi-core machines
+        cpu_percent: f32,
+        memory_bytes: u64,
+    },
+    /// The sidecar was launched but its process is gone
+    Exited { pid: u32 },
+    /// An external backend (e.g. a dev server) whose process Dipper doesn't own
+    NotManaged,
+}
+
+/// CPU and memory of the backend sidecar Dipper launched
+#[tauri::command]
+async fn get_server_stats(state: tauri::State<'_, BackendState>) -> Result<ServerStats, String> {
+    let Some(pid) = state.process.lock().unwrap().as_ref().map(|child| child.pid()) else {
+        return Ok(ServerStats::NotManaged);
+    };
+    tauri::async_runtime::spawn_blocking(move || match system::process_tree_usage(pid) {
+        Some((cpu_percent, memory_bytes)) => ServerStats::Running { pid, cpu_percent, memory_bytes },
+        None => ServerStats::Exited { pid },
+    })
+    .await
+    .map_err(|e| format!("Failed to read server stats: {}", e))
+}
+
+/// Tauri command to get the backend server port
+#[tauri::command]
+async fn get_backend_port(state: tauri::State<'_, BackendState>) -> Result<u16, String> {
+    state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())
+}
+
+// Version reported by the backend's /health, fetched once per session
+static SERVER_VERSION: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Version of the backend server, for the About panel and bug reports.
+/// Read from `/health` the first time and cached after that.
+#[tauri::command]
+async fn get_server_version(state: tauri::State<'_, BackendState>) -> Result<String, String> {
+    if let Some(version) = SERVER_VERSION.get() {
+        return Ok(version.clone());
+    }
+    let port = state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())?;
+    let health: serde_json::Value = tauri::async_runtime::spawn_blocking(move || {
+        ureq::get(&health_url(port))
+            .timeout(Duration::from_secs(3))
+            .call()
+            .map_err(|e| format!("Failed to reach backend: {}", e))?
+            .into_json()
+            .map_err(|e| format!("Invalid health response: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to query backend version: {}", e))??;
+
+    let version = health.get("version")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Backend does not report a version".to_string())?;
+    Ok(SERVER_VERSION.get_or_init(|| version.to_string()).clone())
+}
+
+/// Version of the Dipper app itself, from the package info
+#[tauri::command]
+async fn get_app_version(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(app.package_info().version.to_string())
+}
+
+/// Stream a file served by the backend straight to disk.
+/// The body is written to a sibling `.part` file and renamed into place once complete,
+/// emitting `download-progress` events along the way.
+#[tauri::command]
+async fn download_backend_file(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BackendState>,
+    downloads: tauri::State<'_, DownloadState>,
+    endpoint_path: String,
+    out_path: String,
+) -> Result<String, String> {
+    let port = state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())?;
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut active = downloads.active.lock().unwrap();
+        if active.contains_key(&out_path) {
+            return Err(format!("A download to {} is already in progress", out_path));
+        }
+        active.insert(out_path.clone(), cancel.clone());
+    }
+
+    let url = format!("http://127.0.0.1:{}/{}", port, endpoint_path.trim_start_matches('/'));
+    let target = out_path.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        stream_to_file(&app, &url, &target, &cancel)
+    })
+    .await
+    .map_err(|e| format!("Download task failed: {}", e))
+    .and_then(|r| r);
+
+    downloads.active.lock().unwrap().remove(&out_path);
+    result.map(|_| out_path)
+}
+
+/// Cancel an in-flight `download_backend_file` targeting `out_path`
+#[tauri::command]
+async fn cancel_download(downloads: tauri::State<'_, DownloadState>, out_path: String) -> Result<(), String> {
+    match downloads.active.lock().unwrap().get(&out_path) {
+        Some(flag) => {
+            flag.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+        None => Err(format!("No download in progress for {}", out_path)),
+    }
+}
+
+/// Blocking worker for `download_backend_file`
+fn stream_to_file(app: &tauri::AppHandle, url: &str, out_path: &str, cancel: &AtomicBool) -> Result<(), String> {
+    // Emit progress at most every 512 KB to avoid flooding the webview
+    const PROGRESS_INTERVAL: u64 = 512 * 1024;
+
+    let response = ureq::get(url)
+        .call()
+        .map_err(|e| format!("Backend request failed: {}", e))?;
+    let total = response.header("Content-Length").and_then(|v| v.parse::<u64>().ok());
+
+    let temp_path = format!("{}.part", out_path);
+    let mut file = fs::File::create(&temp_path)
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+
+    let mut reader = response.into_reader();
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut bytes: u64 = 0;
+    let mut last_emitted: u64 = 0;
+
+    let outcome = loop {
+        if cancel.load(Ordering::SeqCst) {
+            break Err("Download cancelled".to_string());
+        }
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => break Ok(()),
+            Ok(n) => n,
+            Err(e) => break Err(format!("Failed to read response: {}", e)),
+        };
+        if let Err(e) = file.write_all(&buffer[..n]) {
+            break Err(format!("Failed to write file: {}", e));
+        }
+        bytes += n as u64;
+        if bytes - last_emitted >= PROGRESS_INTERVAL {
+            last_emitted = bytes;
+            let _ = app.emit("download-progress", DownloadProgress { out_path: out_path.to_string(), bytes, total });
+        }
+    };
+
+    let outcome = outcome
+        .and_then(|_| file.sync_all().map_err(|e| format!("Failed to write file: {}", e)));
+    drop(file);
+
+    if let Err(e) = outcome {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    fs::rename(&temp_path, out_path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("Failed to move download into place: {}", e)
+    })?;
+    if let Err(e) = strip_quarantine(Path::new(out_path)) {
+        app_eprintln!("Warning: {}", e);
+    }
+
+    let _ = app.emit("download-progress", DownloadProgress { out_path: out_path.to_string(), bytes, total });
+    Ok(())
+}
+
+/// Input requirements a model reports through the backend's `/models/info`.
+/// `sample_rate` is `None` when the model keeps each file's native rate.
+#[derive(Deserialize)]
+struct ModelInputSpec {
+    sample_rate: Option<u32>,
+    channels: u16,
+}
+
+/// A selected file that doesn't match the model's expected input
+#[derive(Serialize)]
+struct CompatibilityIssue {
+    path: String,
+    sample_rate: Option<u32>,
+    channels: Option<u16>,
+    problem: String,
+    /// Whether resampling/downmixing would make the file usable
+    fixable: bool,
+    suggested_action: String,
+}
+
+/// Result of `check_model_audio_compatibility`
+#[derive(Serialize)]
+struct CompatibilityReport {
+    model_sample_rate: Option<u32>,
+    model_channels: u16,
+    compatible_count: usize,
+    issues: Vec<CompatibilityIssue>,
+}
+
+/// Compare a model's expected input (as reported by the backend) against the
+/// selected audio files, flagging files that would need preprocessing
+#[tauri::command]
+async fn check_model_audio_compatibility(
+    state: tauri::State<'_, BackendState>,
+    model_path: String,
+    paths: Vec<String>,
+    env_path: Option<String>,
+) -> Result<CompatibilityReport, String> {
+    let port = state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let url = format!("http://127.0.0.1:{}/models/info", port);
+        let spec: ModelInputSpec = ureq::post(&url)
+            .timeout(Duration::from_secs(60))
+            .send_json(serde_json::json!({ "model_path": model_path, "env_path": env_path }))
+            .map_err(|e| format!("Backend could not report model input requirements: {}", e))?
+            .into_json()
+            .map_err(|e| format!("Failed to parse model info: {}", e))?;
+
+        let mut issues = Vec::new();
+        for path in &paths {
+            let props = match audio::probe(Path::new(path)) {
+                Ok(props) => props,
+                Err(e) => {
+                    issues.push(CompatibilityIssue {
+                        path: path.clone(),
+                        sample_rate: None,
+                        channels: None,
+                        problem: e,
+                        fixable: false,
+                        suggested_action: "Remove the file from the selection".to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let mut problems = Vec::new();
+            let mut actions = Vec::new();
+            let mut fixable = true;
+            if let Some(expected) = spec.sample_rate.filter(|&rate| rate != props.sample_rate) {
+                problems.push(format!("sample rate {} Hz, model expects {} Hz", props.sample_rate, expected));
+                actions.push(format!("resample to {} Hz", expected));
+            }
+            if props.channels != spec.channels {
+                problems.push(format!("{} channel(s), model expects {}", props.channels, spec.channels));
+                if props.channels > spec.channels && spec.channels == 1 {
+                    actions.push("downmix to mono".to_string());
+                } else {
+                    fixable = false;
+                    actions.push(format!("provide {}-channel audio", spec.channels));
+                }
+            }
+
+            if !problems.is_empty() {
+                issues.push(CompatibilityIssue {
+                    path: path.clone(),
+                    sample_rate: Some(props.sample_rate),
+                    channels: Some(props.channels),
+                    problem: problems.join("; "),
+                    fixable,
+                    suggested_action: actions.join(", "),
+                });
+            }
+        }
+
+        Ok(CompatibilityReport {
+            model_sample_rate: spec.sample_rate,
+            model_channels: spec.channels,
+            compatible_count: paths.len() - issues.len(),
+            issues,
+        })
+    })
+    .await
+    .map_err(|e| format!("Compatibility check failed: {}", e))?
+}
+
+/// Remove the macOS `com.apple.quarantine` attribute so Gatekeeper doesn't block the file.
+/// A missing attribute counts as success; this is a no-op on other platforms.
+#[cfg(target_os = "macos")]
+fn strip_quarantine(path: &Path) -> Result<(), String> {
+    let output = std::process::Command::new("xattr")
+        .arg("-d")
+        .arg("com.apple.quarantine")
+        .arg(path)
+        .output()
+        .map_err(|e| format!("Failed to run xattr: {}", e))?;
+
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("No such xattr") {
+        Ok(())
+    } else if stderr.contains("Permission denied") || stderr.contains("Operation not permitted") {
+        Err(format!("Permission denied clearing quarantine on {}", path.display()))
+    } else {
+        Err(format!("Failed to clear quarantine: {}", stderr.trim()))
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn strip_quarantine(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// Clear the macOS quarantine attribute from a file (no-op on other platforms)
+#[tauri::command]
+async fn clear_quarantine(path: String) -> Result<(), String> {
+    let path = PathBuf::from(path);
+    if !path.exists() {
+        return Err(format!("Path does not exist: {}", path.display()));
+    }
+    strip_quarantine(&path)
+}
+
+/// Location of the bundled sidecar binary, which Tauri places next to the app executable
+fn sidecar_binary_path() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let name = if cfg!(windows) { "lightweight_server.exe" } else { "lightweight_server" };
+    Some(exe.parent()?.join(name))
+}
+
+/// Send a JSON request to the backend, waiting for a free slot under the
+/// concurrency limit first. Requests with a `request_id` can be cancelled
+/// through `cancel_backend_request` while they are still queued.
+#[tauri::command]
+async fn backend_request(
+    state: tauri::State<'_, BackendState>,
+    limiter: tauri::State<'_, RequestLimiter>,
+    method: String,
+    path: String,
+    body: Option<serde_json::Value>,
+    request_id: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let port = state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())?;
+
+    let cancel = Arc::new(tokio::sync::Notify::new());
+    if let Some(id) = &request_id {
+        limiter.pending.lock().unwrap().insert(id.clone(), cancel.clone());
+    }
+
+    let acquired = tokio::select! {
+        permit = limiter.semaphore.clone().acquire_owned() => {
+            permit.map_err(|_| "Request limiter closed".to_string())
+        }
+        _ = cancel.notified() => Err("Request cancelled".to_string()),
+    };
+    if let Some(id) = &request_id {
+        limiter.pending.lock().unwrap().remove(id);
+    }
+    let permit = acquired?;
+
+    let url = format!("http://127.0.0.1:{}/{}", port, path.trim_start_matches('/'));
+    tauri::async_runtime::spawn_blocking(move || {
+        let _permit = permit;
+        let request = ureq::request(&method.to_uppercase(), &url);
+        let result = match body {
+            Some(json) => request.send_json(json),
+            None => request.call(),
+        };
+        match result {
+            Ok(response) => {
+                let text = response.into_string()
+                    .map_err(|e| format!("Failed to read backend response: {}", e))?;
+                Ok(serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text)))
+            }
+            Err(ureq::Error::Status(code, response)) => {
+                let text = response.into_string().unwrap_or_default();
+                Err(format!("Backend returned HTTP {}: {}", code, text))
+            }
+            Err(e) => Err(format!("Backend request failed: {}", e)),
+        }
+    })
+    .await
+    .map_err(|e| format!("Backend request task failed: {}", e))?
+}
+
+/// Cancel a queued `backend_request` that hasn't been sent yet
+#[tauri::command]
+async fn cancel_backend_request(limiter: tauri::State<'_, RequestLimiter>, request_id: String) -> Result<(), String> {
+    match limiter.pending.lock().unwrap().remove(&request_id) {
+        Some(cancel) => {
+            cancel.notify_one();
+            Ok(())
+        }
+        None => Err(format!("No queued request with id {}", request_id)),
+    }
+}
+
+/// Get the maximum number of concurrent backend requests
+#[tauri::command]
+async fn get_max_concurrent_requests(limiter: tauri::State<'_, RequestLimiter>) -> Result<usize, String> {
+    Ok(*limiter.limit.lock().unwrap())
+}
+
+/// Change and persist the maximum number of concurrent backend requests
+#[tauri::command]
+async fn set_max_concurrent_requests(
+    app: tauri::AppHandle,
+    limiter: tauri::State<'_, RequestLimiter>,
+    limit: usize,
+) -> Result<(), String> {
+    if limit == 0 {
+        return Err("Concurrent request limit must be at least 1".to_string());
+    }
+
+    {
+        let mut current = limiter.limit.lock().unwrap();
+        if limit > *current {
+            limiter.semaphore.add_permits(limit - *current);
+        } else if limit < *current {
+            // Permits held by in-flight requests can't be forgotten yet, so
+            // reclaim the remainder as those requests finish
+            let excess = *current - limit;
+            let forgotten = limiter.semaphore.forget_permits(excess);
+            if forgotten < excess {
+                let semaphore = limiter.semaphore.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Ok(permits) = semaphore.acquire_many_owned((excess - forgotten) as u32).await {
+                        permits.forget();
+                    }
+                });
+            }
+        }
+        *current = limit;
+    }
+
+    settings::set(&app, MAX_CONCURRENT_REQUESTS_KEY, limit)
+}
+
+/// Render a grid of spectrogram thumbnails, one per file, captioned with file
+/// names. Thumbnails come from the backend's `/clip` endpoint, so repeated
+/// sheets over the same files hit its spectrogram cache.
+#[tauri::command]
+async fn contact_sheet(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BackendState>,
+    sheet_state: tauri::State<'_, ContactSheetState>,
+    paths: Vec<String>,
+    columns: usize,
+    thumb_size: [u32; 2],
+    out_path: String,
+) -> Result<String, String> {
+    // Seconds of audio rendered into each thumbnail
+    const THUMB_SECONDS: f64 = 10.0;
+
+    let [thumb_width, thumb_height] = thumb_size;
+    if thumb_width == 0 || thumb_height == 0 {
+        return Err("Thumbnail size must be non-zero".to_string());
+    }
+    let port = state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())?;
+
+    let cancel = sheet_state.cancel.clone();
+    cancel.store(false, Ordering::SeqCst);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        use base64::Engine;
+
+        let url = format!("http://127.0.0.1:{}/clip", port);
+        let total = paths.len();
+        let mut sheet = contact_sheet::Sheet::new(total, columns, thumb_width, thumb_height);
+
+        for (i, path) in paths.iter().enumerate() {
+            if cancel.load(Ordering::SeqCst) {
+                return Err("Contact sheet cancelled".to_string());
+            }
+
+            let png = ureq::get(&url)
+                .query("file_path", path)
+                .query("start_time", "0")
+                .query("end_time", &THUMB_SECONDS.to_string())
+                .query("image_width", &thumb_width.to_string())
+                .query("image_height", &thumb_height.to_string())
+                .call()
+                .ok()
+                .and_then(|r| r.into_json::<serde_json::Value>().ok())
+                .and_then(|json| json.get("spectrogram_base64")?.as_str().map(str::to_string))
+                .and_then(|b64| base64::engine::general_purpose::STANDARD.decode(b64).ok());
+
+            let caption = Path::new(path).file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            sheet.place(i, png.as_deref(), &caption);
+
+            let _ = app.emit("contact-sheet-progress", Progress { done: i + 1, total });
+        }
+
+        sheet.save(&out_path)?;
+        Ok(out_path)
+    })
+    .await
+    .map_err(|e| format!("Contact sheet task failed: {}", e))?
+}
+
+/// Cancel the running `contact_sheet` job
+#[tauri::command]
+async fn cancel_contact_sheet(sheet_state: tauri::State<'_, ContactSheetState>) -> Result<(), String> {
+    sheet_state.cancel.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Fetch the backend's `/capabilities` manifest. Older backends without it are
+/// described conservatively from the feature list in `/health`.
+fn fetch_capabilities(port: u16) -> Capabilities {
+    let get = |endpoint: &str| {
+        ureq::get(&format!("http://127.0.0.1:{}/{}", port, endpoint))
+            .timeout(Duration::from_secs(5))
+            .call()
+            .ok()
+            .and_then(|r| r.into_json::<serde_json::Value>().ok())
+    };
+
+    if let Some(capabilities) = get("capabilities").and_then(|json| serde_json::from_value(json).ok()) {
+        return capabilities;
+    }
+
+    let features = get("health")
+        .and_then(|json| serde_json::from_value::<Vec<String>>(json.get("capabilities")?.clone()).ok())
+        .unwrap_or_default();
+    Capabilities { features, ..Default::default() }
+}
+
+/// Get the backend's capabilities, fetched once after startup and cached
+#[tauri::command]
+async fn backend_capabilities(state: tauri::State<'_, BackendState>) -> Result<Capabilities, String> {
+    if let Some(cached) = state.capabilities.lock().unwrap().clone() {
+        return Ok(cached);
+    }
+    let port = state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())?;
+
+    let capabilities = tauri::async_runtime::spawn_blocking(move || fetch_capabilities(port))
+        .await
+        .map_err(|e| format!("Capabilities request failed: {}", e))?;
+    *state.capabilities.lock().unwrap() = Some(capabilities.clone());
+    Ok(capabilities)
+}
+
+const INFERENCE_BATCH_SIZE_KEY: &str = "inference_batch_size";
+const DEFAULT_INFERENCE_BATCH_SIZE: usize = 1;
+const MAX_INFERENCE_BATCH_SIZE: usize = 1024;
+
+/// Get the inference batch size used for new inference runs
+#[tauri::command]
+async fn get_inference_batch_size(app: tauri::AppHandle) -> Result<usize, String> {
+    Ok(settings::get::<usize>(&app, INFERENCE_BATCH_SIZE_KEY)
+        .filter(|n| (1..=MAX_INFERENCE_BATCH_SIZE).contains(n))
+        .unwrap_or(DEFAULT_INFERENCE_BATCH_SIZE))
+}
+
+/// Outcome of `set_inference_batch_size`
+#[derive(Serialize)]
+struct BatchSizeUpdate {
+    batch_size: usize,
+    /// Whether the backend was restarted so the new size is already in effect
+    restarted: bool,
+    /// Largest batch that fits in currently available memory, when `per_file_bytes`
+    /// was given; a `batch_size` above it risks running out of memory
+    recommended_max: Option<usize>,
+}
+
+/// Set the inference batch size. The backend receives it as
+/// `--inference-batch-size` and uses it for inference configs that don't set their
+/// own, so it applies after a restart: immediately with `restart`, otherwise the
+/// next time the backend starts. Pass `per_file_bytes` to check the size against
+/// available memory.
+#[tauri::command]
+async fn set_inference_batch_size(
+    app: tauri::AppHandle,
+    n: usize,
+    restart: Option<bool>,
+    per_file_bytes: Option<u64>,
+) -> Result<BatchSizeUpdate, String> {
+    if !(1..=MAX_INFERENCE_BATCH_SIZE).contains(&n) {
+        return Err(format!("Batch size must be between 1 and {}", MAX_INFERENCE_BATCH_SIZE));
+    }
+    let previous = get_inference_batch_size(app.clone()).await?;
+    settings::set(&app, INFERENCE_BATCH_SIZE_KEY, n)?;
+
+    let recommended_max = per_file_bytes
+        .filter(|&bytes| bytes > 0)
+        .map(|bytes| batch_size_for_memory(system::memory().1, bytes));
+    if let Some(max) = recommended_max.filter(|&max| n > max) {
+        app_eprintln!("Warning: inference batch size {} exceeds the {} that fit in available memory", n, max);
+    }
+
+    let restarted = restart.unwrap_or(false) && n != previous;
+    if restarted {
+        let handle = app.clone();
+        tauri::async_runtime::spawn_blocking(move || restart_backend_server(&handle))
+            .await
+            .map_err(|e| format!("Failed to restart backend: {}", e))??;
+    }
+    Ok(BatchSizeUpdate { batch_size: n, restarted, recommended_max })
+}
+
+/// Physical memory in bytes, for warning before a run that may not fit
+#[derive(Serialize)]
+struct MemInfo {
+    total_bytes: u64,
+    available_bytes: u64,
+}
+
+/// Total and currently available system memory
+#[tauri::command]
+async fn get_system_memory() -> Result<MemInfo, String> {
+    let (total_bytes, available_bytes) = system::memory();
+    Ok(MemInfo { total_bytes, available_bytes })
+}
+
+// Share of available memory a run's batch may use; the rest is left for the model,
+// the OS and other apps
+const BATCH_MEMORY_FRACTION: f64 = 0.75;
+
+/// Largest inference batch size whose files fit in the usable share of
+/// `available_bytes`, given roughly `per_file_bytes` per file. Always at least 1.
+#[tauri::command]
+async fn recommended_batch_size(available_bytes: u64, per_file_bytes: u64) -> Result<usize, String> {
+    if per_file_bytes == 0 {
+        return Err("Per-file memory must be greater than zero".to_string());
+    }
+    Ok(batch_size_for_memory(available_bytes, per_file_bytes))
+}
+
+fn batch_size_for_memory(available_bytes: u64, per_file_bytes: u64) -> usize {
+    let usable = (available_bytes as f64 * BATCH_MEMORY_FRACTION) as u64;
+    ((usable / per_file_bytes) as usize).clamp(1, MAX_INFERENCE_BATCH_SIZE)
+}
+
+/// Read a frontend preference from the settings file; `None` when unset
+#[tauri::command]
+async fn get_setting(app: tauri::AppHandle, key: String) -> Result<Option<serde_json::Value>, String> {
+    Ok(settings::get(&app, &key))
+}
+
+/// Store a frontend preference in the settings file, creating it on first write
+#[tauri::command]
+async fn set_setting(app: tauri::AppHandle, key: String, value: serde_json::Value) -> Result<(), String> {
+    settings::set(&app, &key, value)
+}
+
+/// Machine and install details for support requests
+#[derive(Serialize)]
+struct EnvSnapshot {
+    os: String,
+    os_version: String,
+    arch: String,
+    cpu: String,
+    cpu_cores: Option<usize>,
+    total_memory_bytes: u64,
+    available_memory_bytes: u64,
+    gpu: Option<String>,
+    app_version: String,
+    tauri_version: String,
+    backend_port: Option<u16>,
+    sidecar_path: Option<String>,
+    config_dir: Option<String>,
+    data_dir: Option<String>,
+    free_disk_bytes: Option<u64>,
+}
+
+/// Gather the environment snapshot, with the home directory redacted from paths
+fn collect_environment_snapshot(app: &tauri::AppHandle) -> EnvSnapshot {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    sys.refresh_cpu_all();
+    let info = os_info::get();
+
+    let config_dir = app.path().app_config_dir().ok();
+    let data_dir = app.path().app_data_dir().ok();
+    let free_disk_bytes = data_dir.as_deref()
+        .or(config_dir.as_deref())
+        .and_then(|dir| system::disk_space(dir.parent().unwrap_or(dir)))
+        .map(|(available, _)| available);
+    let redact = |p: Option<PathBuf>| p.map(|p| system::redact_home(&p.to_string_lossy()));
+
+    let state: tauri::State<BackendState> = app.state();
+    let backend_port = *state.port.lock().unwrap();
+
+    EnvSnapshot {
+        os: info.os_type().to_string(),
+        os_version: info.version().to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpu: sys.cpus().first().map(|c| c.brand().trim().to_string()).unwrap_or_default(),
+        cpu_cores: sysinfo::System::physical_core_count(),
+        total_memory_bytes: sys.total_memory(),
+        available_memory_bytes: sys.available_memory(),
+        gpu: system::gpu_name(),
+        app_version: app.package_info().version.to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        backend_port,
+        sidecar_path: redact(sidecar_binary_path()),
+        config_dir: redact(config_dir),
+        data_dir: redact(data_dir),
+        free_disk_bytes,
+    }
+}
+
+/// Capture OS, hardware, version, and path details for support requests
+#[tauri::command]
+async fn environment_snapshot(app: tauri::AppHandle) -> Result<EnvSnapshot, String> {
+    tauri::async_runtime::spawn_blocking(move || collect_environment_snapshot(&app))
+        .await
+        .map_err(|e| format!("Failed to collect environment snapshot: {}", e))
+}
+
+/// Reload the main window's webview while keeping the running backend alive.
+/// Startup only happens in `setup`, so the reloaded UI reattaches to the existing
+/// server through `get_backend_port` instead of spawning a new one.
+#[tauri::command]
+async fn reload_frontend(app: tauri::AppHandle) -> Result<(), String> {
+    let main_window = app.get_webview_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+    main_window.reload()
+        .map_err(|e| format!("Failed to reload frontend: {}", e))
+}
+
+// How long to wait for the backend to exit on its own before force-killing it
+const BACKEND_SHUTDOWN_GRACE: Duration = Duration::from_millis(1500);
+
+/// Stop the backend sidecar if Dipper started it; a pre-existing dev backend is left alone.
+/// On Unix the server first gets SIGTERM so it can clean up, and is killed outright if
+/// its port is still bound after `BACKEND_SHUTDOWN_GRACE`.
+/// Servers from `start_server` are stopped the same way.
+fn stop_backend_server(app: &tauri::AppHandle) {
+    let registry: tauri::State<ServerRegistry> = app.state();
+    let mut sidecars: Vec<_> = std::mem::take(&mut *registry.servers.lock().unwrap())
+        .into_values()
+        .map(|server| (server.child, Some(server.port)))
+        .collect();
+
+    let state: tauri::State<BackendState> = app.state();
+    state.shutting_down.store(true, Ordering::SeqCst);
+    match state.process.lock().unwrap().take() {
+        Some(child) => sidecars.push((child, *state.port.lock().unwrap())),
+        None => app_println!("No backend process to terminate (may be manual mode)"),
+    }
+    // One shared grace period, so extra servers don't each add to the shutdown delay
+    terminate_sidecars(sidecars);
+}
+
+/// Stop the primary sidecar and start a fresh one on the same port, so it picks up
+/// changed launch arguments. Fails for a backend Dipper didn't start.
+fn restart_backend_server(app: &tauri::AppHandle) -> Result<(), String> {
+    let state: tauri::State<BackendState> = app.state();
+    if state.restarting.swap(true, Ordering::SeqCst) {
+        return Err("The backend is already restarting".to_string());
+    }
+    let result = replace_backend_process(app, &state);
+    state.restarting.store(false, Ordering::SeqCst);
+    result
+}
+
+fn replace_backend_process(app: &tauri::AppHandle, state: &BackendState) -> Result<(), String> {
+    let port = state.port.lock().unwrap()
+        .ok_or_else(|| "Backend port not initialized".to_string())?;
+    let child = state.process.lock().unwrap().take()
+        .ok_or_else(|| "The backend wasn't started by Dipper; restart it manually to apply the change".to_string())?;
+    app_println!("Restarting backend on port {} to apply new settings", port);
+    terminate_sidecar(child, Some(port));
+
+    let child = start_backend_server(app, port)
+        .ok_or_else(|| "Failed to start backend server".to_string())?;
+    *state.process.lock().unwrap() = Some(child);
+    if !wait_for_server(port, &RetryPolicy::startup(), |_, _| {}) {
+        return Err(format!("Backend server on port {} did not become ready after restart", port));
+    }
+    *state.capabilities.lock().unwrap() = Some(fetch_capabilities(port));
+    Ok(())
+}
+
+/// SIGTERM a sidecar (on Unix) and wait up to `BACKEND_SHUTDOWN_GRACE` for `port` to
+/// be released, then kill it
+fn terminate_sidecar(child: tauri_plugin_shell::process::CommandChild, port: Option<u16>) {
+    terminate_sidecars(vec![(child, port)]);
+}
+
+/// `terminate_sidecar` for several sidecars at once: all get SIGTERM first, then
+/// share a single `BACKEND_SHUTDOWN_GRACE` wait for their ports before any that are
+/// still running are killed
+fn terminate_sidecars(sidecars: Vec<(tauri_plugin_shell::process::CommandChild, Option<u16>)>) {
+    #[cfg(unix)]
+    let sidecars = {
+        // Only sidecars that took the signal and have a port to watch can exit gracefully
+        let (mut waiting, mut rest): (Vec<_>, Vec<_>) = sidecars.into_iter().partition(|(child, port)| {
+            port.is_some() && std::process::Command::new("kill")
+                .args(["-TERM", &child.pid().to_string()])
+                .status()
+                .is_ok_and(|status| status.success())
+        });
+        let deadline = std::time::Instant::now() + BACKEND_SHUTDOWN_GRACE;
+        while !waiting.is_empty() && std::time::Instant::now() < deadline {
+            // A free port means the server has shut down
+            let (exited, still_running): (Vec<_>, Vec<_>) = waiting.into_iter()
+                .partition(|(_, port)| port.is_some_and(|port| TcpListener::bind(("127.0.0.1", port)).is_ok()));
+            for (child, _) in exited {
+                app_println!("✓ Backend server (PID {}) exited gracefully", child.pid());
+                let _ = child.kill();
+            }
+            waiting = still_running;
+            if !waiting.is_empty() {
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+        rest.append(&mut waiting);
+        rest
+    };
+
+    for (child, _) in sidecars {
+        app_println!("Killing backend server (PID {})...", child.pid());
+        let _ = child.kill();
+        app_println!("✓ Backend server terminated");
+    }
+}
+
+/// A backend started with `start_server`, running alongside the primary one
+struct ServerInstance {
+    port: u16,
+    child: tauri_plugin_shell::process::CommandChild,
 }
 
-/// Select multiple files
-#[tauri::command]
-async fn select_files(app: tauri::AppHandle) -> Result<Vec<String>, String> {
-    let (tx, rx) = std::sync::mpsc::channel();
+// Extra backends keyed by id, for running independent analyses in parallel
+#[derive(Default)]
+struct ServerRegistry {
+    servers: Mutex<BTreeMap<u64, ServerInstance>>,
+    /// Ports picked by a `start_server` whose sidecar isn't registered yet
+    reserved_ports: Mutex<Vec<u16>>,
+    next_id: std::sync::atomic::AtomicU64,
+}
 
-    app.dialog()
-        .file()
-        .add_filter("Audio Files", &["wav", "mp3", "flac", "ogg", "m4a"])
-        .add_filter("All Files", &["*"])
-        .pick_files(move |files| {
-            tx.send(files).ok();
-        });
+// Id `list_servers` reports for the primary backend started with the app
+const PRIMARY_SERVER_ID: u64 = 0;
 
-    match rx.recv() {
-        Ok(Some(paths)) => Ok(paths.iter().map(|p| p.to_string()).collect()),
-        Ok(None) => Err("No files selected".to_string()),
-        Err(_) => Err("Failed to receive selection".to_string())
-    }
+/// One backend server the frontend can send requests to
+#[derive(Serialize)]
+struct ServerHandle {
+    id: u64,
+    port: u16,
+    /// `None` for an external dev backend Dipper didn't launch
+    pid: Option<u32>,
 }
 
-/// Select a single folder
+/// Launch an additional backend on its own free port and wait until it accepts
+/// requests. Its output goes to the same log as the primary backend's. Unlike the
+/// primary, it is not restarted if it crashes.
 #[tauri::command]
-async fn select_folder(app: tauri::AppHandle) -> Result<String, String> {
-    let (tx, rx) = std::sync::mpsc::channel();
+async fn start_server(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, BackendState>,
+    registry: tauri::State<'_, ServerRegistry>,
+) -> Result<ServerHandle, String> {
+    // A just-spawned server may not have bound its port yet, so skip known ones.
+    // The port is reserved under the lock, so concurrent calls can't both pick it.
+    let port = {
+        let mut reserved = registry.reserved_ports.lock().unwrap();
+        let taken: Vec<u16> = registry.servers.lock().unwrap().values()
+            .map(|server| server.port)
+            .chain(*state.port.lock().unwrap())
+            .chain(reserved.iter().copied())
+            .collect();
+        let port = BACKEND_PORT_RANGE
+            .filter(|port| !taken.contains(port))
+            .find(|&port| TcpListener::bind(("127.0.0.1", port)).is_ok())
+            .ok_or_else(|| format!(
+                "No free port between {} and {} for another backend server",
+                BACKEND_PORT_RANGE.start(),
+                BACKEND_PORT_RANGE.end()
+            ))?;
+        reserved.push(port);
+        port
+    };
 
-    app.dialog()
-        .file()
-        .pick_folder(move |folder| {
-            tx.send(folder).ok();
-        });
+    let child = start_backend_server(&app, port);
+    let id = registry.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+    let pid = child.as_ref().map(|child| child.pid());
+    if let Some(child) = child {
+        registry.servers.lock().unwrap().insert(id, ServerInstance { port, child });
+    }
+    registry.reserved_ports.lock().unwrap().retain(|&reserved| reserved != port);
+    let pid = pid.ok_or_else(|| "Failed to start backend server".to_string())?;
 
-    match rx.recv() {
-        Ok(Some(path)) => Ok(path.to_string()),
-        Ok(None) => Err("No folder selected".to_string()),
-        Err(_) => Err("Failed to receive selection".to_string())
+    let ready = tauri::async_runtime::spawn_blocking(move || wait_for_server(port, &RetryPolicy::startup(), |_, _| {}))
+        .await
+        .map_err(|e| format!("Failed to wait for backend server: {}", e))?;
+    if !ready {
+        if let Some(server) = registry.servers.lock().unwrap().remove(&id) {
+            let _ = server.child.kill();
+        }
+        return Err(format!("Backend server on port {} did not become ready", port));
     }
+    Ok(ServerHandle { id, port, pid: Some(pid) })
 }
 
-/// Select CSV or PKL files for predictions
+/// Stop a backend started with `start_server`
 #[tauri::command]
-async fn select_csv_files(app: tauri::AppHandle) -> Result<Vec<String>, String> {
-    let (tx, rx) = std::sync::mpsc::channel();
-
-    app.dialog()
-        .file()
-        .add_filter("Prediction Files", &["csv", "pkl"])
-        .add_filter("CSV Files", &["csv"])
-        .add_filter("PKL Files", &["pkl"])
-        .add_filter("All Files", &["*"])
-        .pick_files(move |files| {
-            tx.send(files).ok();
-        });
-
-    match rx.recv() {
-        Ok(Some(paths)) => Ok(paths.iter().map(|p| p.to_string()).collect()),
-        Ok(None) => Err("No files selected".to_string()),
-        Err(_) => Err("Failed to receive selection".to_string())
+async fn stop_server(registry: tauri::State<'_, ServerRegistry>, id: u64) -> Result<(), String> {
+    if id == PRIMARY_SERVER_ID {
+        return Err("The primary backend can't be stopped; it is managed by Dipper".to_string());
     }
+    let server = registry.servers.lock().unwrap().remove(&id)
+        .ok_or_else(|| format!("No backend server with id {}", id))?;
+    tauri::async_runtime::spawn_blocking(move || terminate_sidecar(server.child, Some(server.port)))
+        .await
+        .map_err(|e| format!("Failed to stop backend server: {}", e))
 }
 
-/// Select text files
+/// The primary backend (id 0) followed by any started with `start_server`
 #[tauri::command]
-async fn select_text_files(app: tauri::AppHandle) -> Result<Vec<String>, String> {
-    let (tx, rx) = std::sync::mpsc::channel();
+async fn list_servers(
+    state: tauri::State<'_, BackendState>,
+    registry: tauri::State<'_, ServerRegistry>,
+) -> Result<Vec<ServerHandle>, String> {
+    let primary = state.port.lock().unwrap().map(|port| ServerHandle {
+        id: PRIMARY_SERVER_ID,
+        port,
+        pid: state.process.lock().unwrap().as_ref().map(|child| child.pid()),
+    });
+    let extra: Vec<ServerHandle> = registry.servers.lock().unwrap().iter()
+        .map(|(&id, server)| ServerHandle { id, port: server.port, pid: Some(server.child.pid()) })
+        .collect();
+    Ok(primary.into_iter().chain(extra).collect())
+}
 
-    app.dialog()
-        .file()
-        .add_filter("Text Files", &["txt", "csv"])
-        .add_filter("All Files", &["*"])
-        .pick_files(move |files| {
-            tx.send(files).ok();
-        });
+// Settings key for how many times a crashed backend is respawned before giving up
+const BACKEND_RESTART_RETRIES_KEY: &str = "backend_restart_retries";
+const DEFAULT_BACKEND_RESTART_RETRIES: u32 = 3;
 
-    match rx.recv() {
-        Ok(Some(paths)) => Ok(paths.iter().map(|p| p.to_string()).collect()),
-        Ok(None) => Err("No files selected".to_string()),
-        Err(_) => Err("Failed to receive selection".to_string())
-    }
-}
+// Seconds between supervisor health checks
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
-/// Select JSON files
-#[tauri::command]
-async fn select_json_files(app: tauri::AppHandle) -> Result<Vec<String>, String> {
-    let (tx, rx) = std::sync::mpsc::channel();
+// Failed checks in a row before the backend is considered dead; a busy server can
+// miss one health check without being restarted
+const SUPERVISOR_FAILURE_THRESHOLD: u32 = 3;
 
-    app.dialog()
-        .file()
-        .add_filter("JSON Files", &["json"])
-        .add_filter("All Files", &["*"])
-        .pick_files(move |files| {
-            tx.send(files).ok();
-        });
+/// Payload for `server-restarted`
+#[derive(Clone, Serialize)]
+struct ServerRestarted {
+    port: u16,
+    attempt: u32,
+}
 
-    match rx.recv() {
-        Ok(Some(paths)) => Ok(paths.iter().map(|p| p.to_string()).collect()),
-        Ok(None) => Err("No files selected".to_string()),
-        Err(_) => Err("Failed to receive selection".to_string())
-    }
+/// Payload for `server-dead`
+#[derive(Clone, Serialize)]
+struct ServerDead {
+    port: u16,
+    attempts: u32,
 }
 
-/// Select model files
-#[tauri::command]
-async fn select_model_files(app: tauri::AppHandle) -> Result<Vec<String>, String> {
-    let (tx, rx) = std::sync::mpsc::channel();
+/// Quiet single health probe used by the supervisor and the status commands. Like
+/// `is_dipper_health_response`, only a 200 carrying the Dipper signature counts, so an
+/// unrelated server on the port isn't mistaken for the backend.
+fn backend_is_healthy(port: u16) -> bool {
+    ureq::get(&health_url(port))
+        .timeout(Duration::from_secs(3))
+        .call()
+        .ok()
+        .filter(|response| response.status() == 200)
+        .and_then(|response| response.into_json::<serde_json::Value>().ok())
+        .is_some_and(|json| is_dipper_health(&json))
+}
 
-    app.dialog()
-        .file()
-        .add_filter("Model Files", &["pth", "pt", "pickle", "pkl", "model"])
-        .add_filter("All Files", &["*"])
-        .pick_files(move |files| {
-            tx.send(files).ok();
-        });
+/// Watch the backend Dipper started and respawn it, on the same port, if it stops
+/// answering health checks. Emits `server-restarted` after a successful respawn and
+/// `server-dead` once the configured retries are used up. Returns when the app
+/// begins shutting down.
+fn supervise_backend(app: tauri::AppHandle, port: u16) {
+    let state: tauri::State<BackendState> = app.state();
+    let max_retries = settings::get::<u32>(&app, BACKEND_RESTART_RETRIES_KEY)
+        .unwrap_or(DEFAULT_BACKEND_RESTART_RETRIES);
+    let mut failures = 0;
 
-    match rx.recv() {
-        Ok(Some(paths)) => Ok(paths.iter().map(|p| p.to_string()).collect()),
-        Ok(None) => Err("No files selected".to_string()),
-        Err(_) => Err("Failed to receive selection".to_string())
-    }
-}
+    loop {
+        thread::sleep(SUPERVISOR_POLL_INTERVAL);
+        if state.shutting_down.load(Ordering::SeqCst) {
+            return;
+        }
+        // A deliberate restart is waiting on its own child; don't count it as a failure
+        if state.restarting.load(Ordering::SeqCst) {
+            failures = 0;
+            continue;
+        }
+        if backend_is_healthy(port) {
+            failures = 0;
+            continue;
+        }
+        failures += 1;
+        if failures < SUPERVISOR_FAILURE_THRESHOLD {
+            continue;
+        }
 
-/// Show save file dialog and return the selected path
-#[tauri::command]
-async fn save_file(app: tauri::AppHandle, default_name: String) -> Result<String, String> {
-    let (tx, rx) = std::sync::mpsc::channel();
+        app_eprintln!("✗ Backend on port {} stopped responding, restarting...", port);
+        let mut attempt = 0;
+        let restarted = loop {
+            if attempt == max_retries || state.shutting_down.load(Ordering::SeqCst) {
+                break false;
+            }
+            attempt += 1;
+            // Back off 2s, 4s, 8s, ... between attempts
+            thread::sleep(Duration::from_secs(1 << attempt.min(5)));
 
-    // Determine file type from extension
-    let is_json = default_name.to_lowercase().contains(".json");
+            if let Some(old) = state.process.lock().unwrap().take() {
+                terminate_sidecar(old, Some(port));
+            }
+            // The frontend caches the backend URL, so the respawn must reuse the port;
+            // if it's still held, wait for the next attempt
+            if TcpListener::bind(("127.0.0.1", port)).is_err() {
+                app_eprintln!("  Port {} is still in use", port);
+                continue;
+            }
 
-    let mut dialog = app.dialog()
-        .file()
-        .set_file_name(&default_name);
+            let Some(child) = start_backend_server(&app, port) else {
+                continue;
+            };
+            {
+                // Checked under the lock so stop_backend_server always sees the new child
+                let mut process = state.process.lock().unwrap();
+                if state.shutting_down.load(Ordering::SeqCst) {
+                    let _ = child.kill();
+                    return;
+                }
+                *process = Some(child);
+            }
+            if wait_for_server(port, &RetryPolicy::with_deadline(Duration::from_secs(30)), |_, _| {}) {
+                break true;
+            }
+        };
 
-    if is_json {
-        dialog = dialog.add_filter("JSON Files", &["json"]);
-    } else {
-        dialog = dialog.add_filter("CSV Files", &["csv"]);
+        if restarted {
+            app_println!("✓ Backend restarted on port {} (attempt {})", port, attempt);
+            *state.port.lock().unwrap() = Some(port);
+            *state.capabilities.lock().unwrap() = Some(fetch_capabilities(port));
+            let _ = app.emit("server-restarted", ServerRestarted { port, attempt });
+            failures = 0;
+        } else {
+            if !state.shutting_down.load(Ordering::SeqCst) {
+                app_eprintln!("✗ Backend could not be restarted after {} attempts", attempt);
+                let _ = app.emit("server-dead", ServerDead { port, attempts: attempt });
+            }
+            return;
+        }
     }
-    dialog = dialog.add_filter("All Files", &["*"]);
+}
 
-    dialog.save_file(move |path| {
-        tx.send(path).ok();
-    });
+/// Append a backend output line to the ring buffer and queue it for the frontend.
+/// Lines are forwarded in `server-log-batch` events at most every
+/// `SERVER_LOG_BATCH_WINDOW`, so a chatty backend can't flood the webview.
+fn record_server_log(app: &tauri::AppHandle, stream: &'static str, line: String) {
+    let entry = ServerLogLine { stream, line };
+    let state: tauri::State<ServerLogState> = app.state();
+    {
+        let mut lines = state.lines.lock().unwrap();
+        if lines.len() == SERVER_LOG_CAPACITY {
+            lines.pop_front();
+        }
+        lines.push_back(entry.clone());
+    }
 
-    match rx.recv() {
-        Ok(Some(p)) => Ok(p.to_string()),
-        Ok(None) => Err("Save cancelled".to_string()),
-        Err(_) => Err("Failed to receive selection".to_string())
+    let mut pending = state.pending.lock().unwrap();
+    pending.push(entry);
+    // The first line of a window schedules the flush for the whole window
+    if pending.len() == 1 {
+        let app = app.clone();
+        thread::spawn(move || {
+            thread::sleep(SERVER_LOG_BATCH_WINDOW);
+            let state: tauri::State<ServerLogState> = app.state();
+            let batch = std::mem::take(&mut *state.pending.lock().unwrap());
+            let _ = app.emit("server-log-batch", batch);
+        });
     }
 }
 
-/// Write content to a file
+/// Recent backend sidecar output, oldest first
 #[tauri::command]
-async fn write_file(file_path: String, content: String) -> Result<(), String> {
-    fs::write(&file_path, content)
-        .map_err(|e| format!("Failed to write file: {}", e))
+async fn get_server_logs(state: tauri::State<'_, ServerLogState>) -> Result<Vec<ServerLogLine>, String> {
+    Ok(state.lines.lock().unwrap().iter().cloned().collect())
 }
 
-/// Open a file with the system default application
-#[tauri::command]
-async fn open_file(app: tauri::AppHandle, file_path: String) -> Result<(), String> {
-    app.opener()
-        .open_path(&file_path, None::<&str>)
-        .map_err(|e| format!("Failed to open file: {}", e))
-}
+/// Format a time as `YYYY-MM-DD HH:MM:SS` UTC
+fn format_utc(time: std::time::SystemTime) -> String {
+    let secs = time.duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
 
-/// Read text content from a file
-#[tauri::command]
-async fn read_text_file(file_path: String) -> Result<String, String> {
-    fs::read_to_string(&file_path)
-        .map_err(|e| format!("Failed to read file: {}", e))
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year, month, day, rem / 3_600, rem % 3_600 / 60, rem % 60
+    )
 }
 
-/// Generate a unique folder name by appending numeric suffix if needed
+/// Write Dipper's own console messages and the buffered backend output to one text
+/// file for bug reports, headed by the export time, versions, OS, and backend port
 #[tauri::command]
-async fn generate_unique_folder_name(base_path: String, folder_name: String) -> Result<String, String> {
-    let base = PathBuf::from(&base_path);
+async fn export_logs(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let state: tauri::State<BackendState> = app.state();
+    let port = *state.port.lock().unwrap();
+    let port = port.map_or("not started".to_string(), |port| port.to_string());
+    let server_version = SERVER_VERSION.get().cloned().unwrap_or_else(|| "unknown".to_string());
+    let info = os_info::get();
 
-    // Check if base path exists
-    if !base.exists() {
-        return Err(format!("Base path does not exist: {}", base_path));
-    }
+    let mut out = String::new();
+    out.push_str(&format!("Dipper logs exported {} UTC\n", format_utc(std::time::SystemTime::now())));
+    out.push_str(&format!("App version: {}\n", app.package_info().version));
+    out.push_str(&format!("Server version: {}\n", server_version));
+    out.push_str(&format!("OS: {} {} ({})\n", info.os_type(), info.version(), std::env::consts::ARCH));
+    out.push_str(&format!("Backend port: {}\n", port));
 
-    let mut unique_name = folder_name.clone();
-    let mut counter = 1;
+    out.push_str("\n== Dipper ==\n");
+    for (time, line) in APP_LOG.lock().unwrap().iter() {
+        out.push_str(&format!("[{}] {}\n", format_utc(*time), line));
+    }
 
-    loop {
-        let test_path = base.join(&unique_name);
-        if !test_path.exists() {
-            return Ok(unique_name);
-        }
-        unique_name = format!("{}_{}", folder_name, counter);
-        counter += 1;
+    out.push_str("\n== Backend ==\n");
+    let log_state: tauri::State<ServerLogState> = app.state();
+    for entry in log_state.lines.lock().unwrap().iter() {
+        out.push_str(&format!("[{}] {}\n", entry.stream, entry.line));
     }
+
+    write_atomically(Path::new(&path), out.as_bytes()).map_err(|e| format!("Failed to write log file: {}", e))
 }
 
-/// Get a free port from the OS
-fn get_free_port() -> Option<u16> {
-    // Bind to port 0 to let the OS assign a free port
-    match TcpListener::bind(("127.0.0.1", 0)) {
-        Ok(listener) => {
-            match listener.local_addr() {
-                Ok(addr) => Some(addr.port()),
-                Err(_) => None
-            }
-        }
-        Err(_) => None
-    }
-}
-
-/// Check if the Dipper backend server is running on the given port
-/// Returns true only if the server responds to /health with the expected response
-fn check_dipper_backend_running(port: u16) -> bool {
-    // Build the health check URL
-    let url = format!("http://127.0.0.1:{}/health", port);
-
-    // Try to connect and check the health endpoint with a longer timeout
-    match ureq::get(&url).timeout(Duration::from_secs(5)).call() {
-        Ok(response) => {
-            let status_code = response.status();
-            println!("  Health check got HTTP {}", status_code);
-
-            if status_code == 200 {
-                // Try to parse the JSON response
-                match response.into_string() {
-                    Ok(body) => {
-                        println!("  Health response body: {}", body);
-                        match serde_json::from_str::<serde_json::Value>(&body) {
-                            Ok(json) => {
-                                // Verify it's the Dipper backend by checking for expected fields
-                                let status = json.get("status").and_then(|v| v.as_str());
-                                let server_type = json.get("server_type").and_then(|v| v.as_str());
-
-                                println!("  Parsed: status={:?}, server_type={:?}", status, server_type);
-
-                                if status == Some("ok") && server_type == Some("lightweight") {
-                                    println!("  ✓ Valid Dipper backend detected!");
-                                    return true;
-                                } else {
-                                    println!("  ✗ Response doesn't match Dipper backend signature");
-                                }
-                            }
-                            Err(e) => {
-                                println!("  ✗ Failed to parse JSON: {}", e);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        println!("  ✗ Failed to read response body: {}", e);
-                    }
-                }
-            } else {
-                println!("  ✗ Unexpected status code: {}", status_code);
+// Environment variable that overrides where the backend caches models
+const MODEL_DIR_ENV: &str = "DIPPER_MODEL_DIR";
+
+/// App config and data directories, resolved and created during setup
+#[derive(Clone, Serialize)]
+pub struct AppDirs {
+    config_dir: Option<String>,
+    data_dir: Option<String>,
+    /// Why the directories couldn't be created; persistence is disabled when set
+    pub error: Option<String>,
+}
+
+/// Create the app config and data dirs. On a machine where they can't be created
+/// (e.g. a read-only profile) the app keeps running without persistence: the
+/// failure is logged, kept for `get_app_dirs`, and sent as `storage-unavailable`
+/// by `announce_storage_error` once the main window has loaded (an event emitted
+/// during setup would have no listener yet).
+fn ensure_app_dirs(app: &tauri::AppHandle) -> AppDirs {
+    let mut errors = Vec::new();
+    let mut ensure = |label: &str, dir: tauri::Result<PathBuf>| match dir {
+        Ok(dir) => {
+            if let Err(e) = fs::create_dir_all(&dir) {
+                errors.push(format!("Failed to create {} directory {}: {}", label, dir.display(), e));
             }
-            false
+            Some(dir.to_string_lossy().to_string())
         }
         Err(e) => {
-            println!("  ✗ Connection failed: {}", e);
-            false
+            errors.push(format!("Failed to resolve {} directory: {}", label, e));
+            None
         }
+    };
+    let config_dir = ensure("config", app.path().app_config_dir());
+    let data_dir = ensure("data", app.path().app_data_dir());
+
+    let error = (!errors.is_empty()).then(|| errors.join("; "));
+    if let Some(error) = &error {
+        app_eprintln!("Warning: {} (settings will not be saved)", error);
     }
+    AppDirs { config_dir, data_dir, error }
 }
 
-/// Wait for the Dipper backend to be ready on the given port
-fn wait_for_server(port: u16, max_retries: u32) -> bool {
-    for i in 0..max_retries {
-        if check_dipper_backend_running(port) {
-            println!("✓ Dipper backend health check passed on port {}!", port);
-            return true;
-        }
-        if i < 3 || i % 5 == 0 {
-            // Only print every 5th attempt after the first 3 to reduce spam
-            println!("⏳ Checking backend health on port {}... (attempt {}/{})", port, i + 1, max_retries);
-        }
-        thread::sleep(Duration::from_secs(2));
+/// Emit `storage-unavailable` with the error from `ensure_app_dirs`, if there was one,
+/// after each load of the main window's page
+fn announce_storage_error(webview: &tauri::Webview, payload: &tauri::webview::PageLoadPayload<'_>) {
+    if webview.label() != "main" || payload.event() != tauri::webview::PageLoadEvent::Finished {
+        return;
+    }
+    // The page can finish loading before setup has managed `AppDirs`
+    let Some(dirs) = webview.try_state::<AppDirs>() else {
+        return;
+    };
+    if let Some(error) = &dirs.error {
+        let _ = webview.emit_to("main", "storage-unavailable", error);
     }
-    eprintln!("✗ Backend health check timed out after {} attempts", max_retries);
-    false
 }
 
-/// Tauri command to get the backend server port
+/// The app's config and data directories, and why they are unusable if they are
 #[tauri::command]
-async fn get_backend_port(state: tauri::State<'_, BackendState>) -> Result<u16, String> {
-    state.port.lock().unwrap()
-        .ok_or_else(|| "Backend port not initialized".to_string())
+async fn get_app_dirs(dirs: tauri::State<'_, AppDirs>) -> Result<AppDirs, String> {
+    Ok(dirs.inner().clone())
+}
+
+/// Models directory handed to the backend: `DIPPER_MODEL_DIR` if set, otherwise
+/// `models/` in the app data dir. Created if missing.
+fn resolve_model_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = match std::env::var(MODEL_DIR_ENV) {
+        Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => app.path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data dir: {}", e))?
+            .join("models"),
+    };
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create model directory {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// A path as a sidecar argument. Each argument goes to the process as its own argv
+/// entry (no shell), so spaces and non-ASCII characters pass through untouched, but
+/// the Python side decodes argv as UTF-8 and would mangle anything else; such paths
+/// are rejected rather than converted lossily.
+fn sidecar_path_arg(path: &Path) -> Result<String, String> {
+    path.to_str()
+        .map(str::to_string)
+        .ok_or_else(|| format!("Path is not valid UTF-8 and can't be passed to the backend: {}", path.display()))
+}
+
+/// Accelerator bound to each shortcut action ("open_files", "open_folder", "save")
+#[tauri::command]
+async fn get_shortcuts(app: tauri::AppHandle) -> Result<BTreeMap<String, String>, String> {
+    Ok(shortcuts::bindings(&app))
+}
+
+/// Rebind a shortcut action; `None` restores its default. Emits
+/// `shortcut-open-files`, `shortcut-open-folder`, or `shortcut-save` when pressed.
+#[tauri::command]
+async fn set_shortcut(app: tauri::AppHandle, action: String, accelerator: Option<String>) -> Result<(), String> {
+    shortcuts::rebind(&app, &action, accelerator)
+}
+
+// Settings key for environment variables set on the sidecar, e.g. OMP_NUM_THREADS
+const SIDECAR_ENV_KEY: &str = "sidecar_env";
+
+/// Environment variables passed to the backend on top of what Dipper inherited
+#[tauri::command]
+async fn get_sidecar_env(app: tauri::AppHandle) -> Result<BTreeMap<String, String>, String> {
+    Ok(settings::get(&app, SIDECAR_ENV_KEY).unwrap_or_default())
+}
+
+/// Replace the environment variables passed to the backend. Apps launched from
+/// Finder or the Start menu get a minimal environment, so thread counts and GPU
+/// selection are pinned here instead. Applies the next time the backend starts.
+#[tauri::command]
+async fn set_sidecar_env(app: tauri::AppHandle, vars: BTreeMap<String, String>) -> Result<(), String> {
+    for (name, value) in &vars {
+        if name.is_empty() || name.contains(['=', '\0']) {
+            return Err(format!("Invalid environment variable name: {:?}", name));
+        }
+        if value.contains('\0') {
+            return Err(format!("Invalid value for {}", name));
+        }
+    }
+    settings::set(&app, SIDECAR_ENV_KEY, vars)
 }
 
 /// Start the backend HTTP server using Tauri's sidecar mechanism (non-blocking)
 fn start_backend_server(app: &tauri::AppHandle, port: u16) -> Option<tauri_plugin_shell::process::CommandChild> {
-    println!("Starting Dipper backend sidecar on port {}...", port);
+    app_println!("Starting Dipper backend sidecar on port {}...", port);
+
+    // Gatekeeper refuses to launch a quarantined sidecar with an opaque error
+    if let Some(path) = sidecar_binary_path().filter(|p| p.exists()) {
+        if let Err(e) = strip_quarantine(&path) {
+            app_eprintln!("  Warning: {}", e);
+        }
+    }
 
     // Use Tauri's sidecar API to spawn the bundled executable
     let sidecar = match app.shell().sidecar("lightweight_server") {
         Ok(cmd) => {
-            println!("  Sidecar command created successfully");
+            app_println!("  Sidecar command created successfully");
             cmd
         }
         Err(e) => {
-            eprintln!("✗ Failed to create sidecar command: {}", e);
-            eprintln!("  Make sure the binary exists in src-tauri/bin/lightweight_server-*");
+            app_eprintln!("✗ Failed to create sidecar command: {}", e);
+            app_eprintln!("  Make sure the binary exists in src-tauri/bin/lightweight_server-*");
             return None;
         }
     };
 
     // Get current process PID to pass to backend for heartbeat monitoring
     let parent_pid = std::process::id();
-    println!("  Spawning with args: --port {} --parent-pid {}", port, parent_pid);
+    app_println!("  Spawning with args: --port {} --parent-pid {}", port, parent_pid);
+
+    let sidecar = sidecar.args(["--port", &port.to_string(), "--parent-pid", &parent_pid.to_string()]);
+    let sidecar = match resolve_model_dir(app).and_then(|dir| sidecar_path_arg(&dir)) {
+        Ok(model_dir) => {
+            app_println!("  Model directory: {}", model_dir);
+            sidecar.args(["--model-dir", &model_dir])
+        }
+        Err(e) => {
+            app_eprintln!("  Warning: {} (backend will use its default)", e);
+            sidecar
+        }
+    };
+    let sidecar = match settings::get::<usize>(app, INFERENCE_BATCH_SIZE_KEY) {
+        Some(n) if (1..=MAX_INFERENCE_BATCH_SIZE).contains(&n) => {
+            app_println!("  Inference batch size: {}", n);
+            sidecar.args(["--inference-batch-size", &n.to_string()])
+        }
+        _ => sidecar,
+    };
+    let env: BTreeMap<String, String> = settings::get(app, SIDECAR_ENV_KEY).unwrap_or_default();
+    if !env.is_empty() {
+        let names: Vec<&str> = env.keys().map(String::as_str).collect();
+        app_println!("  Environment overrides: {}", names.join(", "));
+    }
+    let sidecar = sidecar.envs(env);
 
-    match sidecar
-        .args(["--port", &port.to_string(), "--parent-pid", &parent_pid.to_string()])
-        .spawn()
+    match sidecar.spawn()
     {
         Ok((mut rx, child)) => {
-            println!("✓ Dipper backend sidecar spawned (PID: {:?})", child.pid());
+            app_println!("✓ Dipper backend sidecar spawned (PID: {:?})", child.pid());
 
             // Spawn a thread to read backend output using blocking receiver
+            let app = app.clone();
             std::thread::spawn(move || {
                 use tauri_plugin_shell::process::CommandEvent;
                 loop {
-                    match rx.blocking_recv() {
+                    let (stream, line) = match rx.blocking_recv() {
                         Some(event) => {
                             match event {
                                 CommandEvent::Stdout(line) => {
-                                    println!("  [Backend stdout] {}", String::from_utf8_lossy(&line));
+                                    let line = String::from_utf8_lossy(&line).trim_end().to_string();
+                                    println!("  [Backend stdout] {}", line);
+                                    ("stdout", line)
                                 }
                                 CommandEvent::Stderr(line) => {
-                                    eprintln!("  [Backend stderr] {}", String::from_utf8_lossy(&line));
+                                    let line = String::from_utf8_lossy(&line).trim_end().to_string();
+                                    eprintln!("  [Backend stderr] {}", line);
+                                    ("stderr", line)
                                 }
                                 CommandEvent::Error(err) => {
                                     eprintln!("  [Backend error] {}", err);
+                                    ("error", err)
                                 }
                                 CommandEvent::Terminated(payload) => {
                                     println!("  [Backend terminated] code: {:?}", payload.code);
+                                    record_server_log(&app, "terminated", format!("Backend exited with code {:?}", payload.code));
                                     break;
                                 }
-                                _ => {}
+                                _ => continue,
                             }
                         }
                         None => {
                             println!("  [Backend output stream closed]");
                             break;
                         }
-                    }
+                    };
+                    record_server_log(&app, stream, line);
                 }
             });
 
             Some(child)
         }
         Err(e) => {
-            eprintln!("✗ Failed to spawn Dipper backend sidecar: {}", e);
-            eprintln!("  Error details: {:?}", e);
+            app_eprintln!("✗ Failed to spawn Dipper backend sidecar: {}", e);
+            app_eprintln!("  Error details: {:?}", e);
             None
         }
     }
 }
 
+/// Flatten dropped files and folders (recursively) into audio files and send them to
+/// the frontend as `files-dropped`. Drops with no audio are ignored.
+fn emit_dropped_audio(app: &tauri::AppHandle, dropped: Vec<PathBuf>) {
+    let mut files = Vec::new();
+    for path in dropped {
+        if path.is_dir() {
+            match scan::collect_audio_files(&path, true, false) {
+                Ok(found) => files.extend(found),
+                Err(e) => app_eprintln!("Failed to scan dropped folder: {}", e),
+            }
+        } else if scan::is_audio_file(&path) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    files.dedup();
+    if files.is_empty() {
+        return;
+    }
+    let files: Vec<String> = files.iter().map(|p| p.to_string_lossy().to_string()).collect();
+    let _ = app.emit("files-dropped", files);
+}
+
+/// Bring the visible window to the front: the main window once startup finished,
+/// otherwise the splash that is still covering it
+fn focus_existing_window(app: &tauri::AppHandle) {
+    let main_window = app.get_webview_window("main")
+        .filter(|window| window.is_visible().unwrap_or(false));
+    let window = main_window.or_else(|| app.get_webview_window("splash"));
+    if let Some(window) = window {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 fn main() {
     tauri::Builder::default()
+        // Must be registered first so a second launch exits before starting another sidecar
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            app_println!("Second instance launched - focusing existing window");
+            focus_existing_window(app);
+        }))
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .on_menu_event(|app, event| shortcuts::handle_menu_event(app, &event))
         .manage(BackendState {
             port: Mutex::new(None),
             process: Mutex::new(None),
+            capabilities: Mutex::new(None),
+            shutting_down: AtomicBool::new(false),
+            restarting: AtomicBool::new(false),
+            startup_status: Mutex::new(None),
+            startup_failure: Mutex::new(None),
+            main_shown: AtomicBool::new(false),
+        })
+        .manage(ServerRegistry::default())
+        .manage(ProjectRootState {
+            root: Mutex::new(None),
+        })
+        .manage(OutputRootsState {
+            roots: Mutex::new(Vec::new()),
+        })
+        .manage(StreamWriteState {
+            writes: Mutex::new(HashMap::new()),
+            next_id: std::sync::atomic::AtomicU64::new(1),
+        })
+        .manage(DownloadState {
+            active: Mutex::new(HashMap::new()),
+        })
+        .manage(ContactSheetState {
+            cancel: Arc::new(AtomicBool::new(false)),
+        })
+        .manage(NormalizeState {
+            cancel: Arc::new(AtomicBool::new(false)),
+        })
+        .manage(jobs::JobState::default())
+        .manage(WatchState {
+            watchers: Mutex::new(HashMap::new()),
+        })
+        .manage(ServerLogState {
+            lines: Mutex::new(VecDeque::with_capacity(SERVER_LOG_CAPACITY)),
+            pending: Mutex::new(Vec::new()),
         })
+        .on_page_load(announce_storage_error)
         .setup(|app| {
+            let app_dirs = ensure_app_dirs(app.handle());
+            app.manage(app_dirs);
+
+            let request_limit = settings::get::<usize>(app.handle(), MAX_CONCURRENT_REQUESTS_KEY)
+                .filter(|&n| n > 0)
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_REQUESTS);
+            app.manage(RequestLimiter {
+                semaphore: Arc::new(tokio::sync::Semaphore::new(request_limit)),
+                limit: Mutex::new(request_limit),
+                pending: Mutex::new(HashMap::new()),
+            });
+
             // Get window handles
             let splash_window = app.get_webview_window("splash").expect("Splash window not found");
             let main_window = app.get_webview_window("main").expect("Main window not found");
 
-            // Load splash HTML content
-            let splash_html = r#"
-<!DOCTYPE html>
-<html>
-
-<head>
-    <meta charset="UTF-8">
-    <style>
-        * {
-            margin: 0;
-            padding: 0;
-            box-sizing: border-box;
-        }
-
-        body {
-            font-family: 'Rokkitt', -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif;
-            background: linear-gradient(135deg, #395756 0%, #4f5d75 100%);
-            display: flex;
-            justify-content: center;
-            align-items: center;
-            height: 100vh;
-            overflow: hidden;
-        }
-
-        .splash-container {
-            text-align: center;
-            color: white;
-        }
-
-        .logo {
-            font-size: 64px;
-            font-weight: 600;
-            margin-bottom: 10px;
-            color: #ffffff;
-        }
-
-        .subtitle {
-            font-size: 18px;
-            color: #c6ac8f;
-            margin-bottom: 10px;
-            margin-top: 10px;
-            font-weight: 300;
-        }
-
-        .loader {
-            width: 40px;
-            height: 40px;
-            border: 4px solid rgba(255, 255, 255, 0.3);
-            border-top: 4px solid #ffffff;
-            border-radius: 50%;
-            margin: 0 auto;
-            animation: spin 1s linear infinite;
-        }
-
-        .status {
-            margin-top: 20px;
-            font-size: 14px;
-            color: rgba(255, 255, 255, 0.8);
-            font-weight: 300;
-        }
-
-        @keyframes spin {
-            0% {
-                transform: rotate(0deg);
-            }
+            // Restore the last size and position before the window is first shown
+            window_state::restore(&main_window);
 
-            100% {
-                transform: rotate(360deg);
+            match shortcuts::install(app.handle(), &main_window) {
+                Ok(state) => {
+                    app.manage(state);
+                }
+                Err(e) => app_eprintln!("Warning: failed to set up keyboard shortcuts: {}", e),
             }
-        }
-    </style>
-</head>
-
-<body>
-    <div class="splash-container">
-        <img src="./icon.svg" alt="Dipper Logo" class="logo" width="200" height="200">
-        <div class="subtitle">Dipper is booting...</div>
-        <div class="loader"></div>
-    </div>
-</body>
-
-</html>
-"#;
-            splash_window.eval(&format!("document.documentElement.innerHTML = `{}`;", splash_html.replace("`", "\\`")))
-                .expect("Failed to load splash HTML");
 
             // Show splash screen immediately
             splash_window.show().expect("Failed to show splash window");
 
-            // Check if Dipper backend is already running on port 8000 (for dev mode with manual backend)
-            let (port, child_process) = if check_dipper_backend_running(8000) {
-                println!("✓ Using existing Dipper backend on port 8000 (dev mode)");
-                (8000, None)
+            // DIPPER_SERVER_PORT pins the port; otherwise look for a manually started
+            // dev backend on 8000 and fall back to a free port for our own sidecar
+            let requested_port = requested_backend_port();
+            let probe_port = requested_port.unwrap_or(DEFAULT_BACKEND_PORT);
+
+            // Check if Dipper backend is already running (for dev mode with manual backend)
+            let identity = identify_server(probe_port, Duration::from_secs(5));
+            let (port, child_process) = if let ServerIdentity::Dipper { .. } = identity {
+                app_println!("✓ Using existing Dipper backend on port {} (dev mode)", probe_port);
+                (probe_port, None)
             } else {
-                // Get a free port from the OS
-                let free_port = get_free_port().expect("Failed to get free port");
-                println!("→ No backend found on port 8000, starting on port {}", free_port);
+                // Never start on, or talk to, a port another service already holds
+                let requested_port = match identity {
+                    ServerIdentity::Other => {
+                        app_eprintln!("  Port {} is in use by a different service; picking another", probe_port);
+                        None
+                    }
+                    _ => requested_port,
+                };
+                let Some(port) = requested_port.or_else(get_free_port) else {
+                    let message = format!(
+                        "No free port between {} and {} for the backend server. Close other local servers and restart Dipper.",
+                        BACKEND_PORT_RANGE.start(),
+                        BACKEND_PORT_RANGE.end()
+                    );
+                    app_eprintln!("✗ {}", message);
+                    show_startup_failure(app.handle(), &message);
+                    return Ok(());
+                };
+                app_println!("→ No backend found on port {}, starting on port {}", probe_port, port);
 
                 // Start our own backend
-                let child = start_backend_server(&app.handle(), free_port);
+                let child = start_backend_server(app.handle(), port);
 
                 if child.is_none() {
-                    eprintln!("✗ Failed to start backend server on port {}", free_port);
-                    eprintln!("  Check that the sidecar binary exists in src-tauri/bin/");
-                    // Continue anyway - app will show connection error
+                    app_eprintln!("✗ Failed to start backend server on port {}", port);
+                    app_eprintln!("  Check that the sidecar binary exists in src-tauri/bin/");
+                    // Continue anyway - the startup wait times out into the splash error state
                 } else {
-                    println!("✓ Backend started successfully on port {}", free_port);
+                    app_println!("✓ Backend started successfully on port {}", port);
                 }
 
-                (free_port, child)
+                (port, child)
             };
+            app_println!("Backend port: {}", port);
 
             // Store port and process in managed state
             let backend_state: tauri::State<BackendState> = app.state();
             *backend_state.port.lock().unwrap() = Some(port);
             *backend_state.process.lock().unwrap() = child_process;
 
-            // Clone handles for background thread
-            let main_window_clone = main_window.clone();
-            let splash_window_clone = splash_window.clone();
-
             // Wait for backend server in background thread
-            thread::spawn(move || {
-                println!("Waiting for backend server to be ready on port {}...", port);
-                if wait_for_server(port, 30) {
-                    println!("✓ Backend server is ready!");
-                    // Show main window and close splash
-                    main_window_clone.show().expect("Failed to show main window");
-                    splash_window_clone.close().expect("Failed to close splash window");
-                } else {
-                    eprintln!("✗ Backend server health check timed out - showing main window anyway");
-                    eprintln!("  (Backend may still be starting up and will work shortly)");
-                    main_window_clone.show().expect("Failed to show main window");
-                    splash_window_clone.close().expect("Failed to close splash window");
-                }
-            });
+            let app_handle = app.handle().clone();
+            thread::spawn(move || await_backend_ready(app_handle, port));
 
             Ok(())
         })
         .on_window_event(|window, event| {
+            if let tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) = event {
+                if window.label() == "main" {
+                    window_state::schedule_save(window.app_handle());
+                }
+            }
+            if let tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) = event {
+                if window.label() == "main" {
+                    let app = window.app_handle().clone();
+                    let paths = paths.clone();
+                    // Dropped folders are scanned off the event loop
+                    thread::spawn(move || emit_dropped_audio(&app, paths));
+                }
+            }
             if let tauri::WindowEvent::CloseRequested { .. } = event {
                 // Only kill backend when MAIN window closes, not splash or other windows
                 if window.label() == "main" {
-                    println!("Main window close event - cleaning up backend...");
-                    let app = window.app_handle();
-                    let state: tauri::State<BackendState> = app.state();
-                    let mut guard = state.process.lock().unwrap();
-                    if let Some(child) = guard.take() {
-                        println!("Killing backend server on window close...");
-                        let _ = child.kill();
-                        println!("✓ Backend server terminated");
-                    } else {
-                        println!("No backend process to terminate (may be manual mode)");
-                    }
-                    drop(guard);
+                    app_println!("Main window close event - cleaning up backend...");
+                    stop_backend_server(window.app_handle());
+                } else if window.label() == "splash"
+                    && window.app_handle().state::<BackendState>().startup_failure.lock().unwrap().is_some()
+                {
+                    // The main window never appeared, so closing the failed splash quits the app
+                    window.app_handle().exit(1);
                 } else {
-                    println!("Window '{}' closed (backend not affected)", window.label());
+                    app_println!("Window '{}' closed (backend not affected)", window.label());
                 }
             }
         })
         .invoke_handler(tauri::generate_handler![
             select_files,
             select_folder,
+            select_folders,
             select_csv_files,
+            inspect_prediction_files,
+            estimate_csv_rows,
             select_text_files,
             select_json_files,
             select_model_files,
+            validate_model_files,
+            verify_pickle,
             save_file,
+            save_file_to,
+            set_project_root,
             write_file,
+            append_file,
+            write_json_file,
+            begin_write,
+            write_chunk,
+            finish_write,
+            write_csv,
+            append_csv,
             open_file,
+            reveal_in_file_manager,
+            open_external,
+            copy_paths_to_clipboard,
             read_text_file,
+            read_file_head,
             generate_unique_folder_name,
-            get_backend_port
+            create_unique_folder,
+            register_output_root,
+            get_recent_outputs,
+            clear_recent_outputs,
+            delete_path,
+            rename_files,
+            gather_files,
+            archive_output,
+            check_free_space,
+            validate_output_folder,
+            preflight_run,
+            scan_folder_for_audio,
+            resume_scan,
+            list_directory,
+            hash_files,
+            find_duplicate_audio,
+            get_audio_info,
+            cancel_scan,
+            cancel_job,
+            job_status,
+            summarize_folder,
+            canonicalize_paths,
+            relativize_paths,
+            batch_paths,
+            resolve_link,
+            normalize_audio_folder,
+            cancel_normalize,
+            detect_recording_groups,
+            merge_recording_group,
+            resolve_root_path,
+            to_tagged_path,
+            estimate_clips_size,
+            detection_rate_timeline,
+            read_guano,
+            export_detections_geojson,
+            watch_folder,
+            unwatch_folder,
+            get_backend_port,
+            get_server_version,
+            probe_server_identity,
+            get_app_version,
+            is_server_running,
+            get_server_status,
+            start_server,
+            stop_server,
+            list_servers,
+            check_remote_server,
+            get_server_stats,
+            retry_backend_start,
+            get_startup_state,
+            cancel_startup,
+            show_main_window,
+            get_server_logs,
+            export_logs,
+            get_app_dirs,
+            get_shortcuts,
+            set_shortcut,
+            get_sidecar_env,
+            set_sidecar_env,
+            download_backend_file,
+            cancel_download,
+            check_model_audio_compatibility,
+            clear_quarantine,
+            reload_frontend,
+            contact_sheet,
+            cancel_contact_sheet,
+            backend_capabilities,
+            get_inference_batch_size,
+            set_inference_batch_size,
+            get_system_memory,
+            recommended_batch_size,
+            get_setting,
+            set_setting,
+            environment_snapshot,
+            backend_request,
+            cancel_backend_request,
+            get_max_concurrent_requests,
+            set_max_concurrent_requests
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
         .run(|app_handle, event| {
             // Handle app-wide exit event to ensure backend cleanup
             if let tauri::RunEvent::Exit = event {
-                println!("App exit event - cleaning up backend...");
-                let state: tauri::State<BackendState> = app_handle.state();
-                let mut guard = state.process.lock().unwrap();
-                if let Some(child) = guard.take() {
-                    println!("Killing backend server on app exit...");
-                    let _ = child.kill();
-                    println!("✓ Backend server terminated");
-                } else {
-                    println!("No backend process to terminate");
-                }
-                drop(guard);
+                app_println!("App exit event - cleaning up backend...");
+                stop_backend_server(app_handle);
+                discard_unfinished_writes(app_handle);
             }
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn sidecar_path_arg_keeps_spaces_and_non_ascii() {
+        for path in [
+            "/Users/field team/Library/Application Support/Dipper/models",
+            "/data/recordings 🐦/site 1",
+            "/home/研究/鳥の声/モデル",
+            "C:\\Users\\José Müller\\AppData\\Roaming\\models",
+        ] {
+            assert_eq!(sidecar_path_arg(Path::new(path)).unwrap(), path);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn sidecar_path_arg_rejects_non_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"/data/caf\xe9"));
+        assert!(sidecar_path_arg(path).is_err());
+    }
+
+    #[test]
+    fn natural_cmp_orders_digit_runs_numerically() {
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file2"), Ordering::Greater);
+        assert_eq!(natural_cmp("file02", "file2"), Ordering::Equal);
+        assert_eq!(natural_cmp("file", "file1"), Ordering::Less);
+        assert_eq!(natural_cmp("a9", "b1"), Ordering::Less);
+        // Longer than any integer type
+        assert_eq!(natural_cmp("x100000000000000000000", "x99999999999999999999"), Ordering::Greater);
+        assert_eq!(natural_cmp("鳥2", "鳥10"), Ordering::Less);
+    }
+
+    #[test]
+    fn sort_paths_compares_file_names_only() {
+        let original = vec!["/z/track10.wav".to_string(), "/a/track2.wav".to_string()];
+
+        let mut paths = original.clone();
+        sort_paths(&mut paths, SortMode::NameNatural);
+        assert_eq!(paths, ["/a/track2.wav", "/z/track10.wav"]);
+
+        let mut paths = original.clone();
+        sort_paths(&mut paths, SortMode::Name);
+        assert_eq!(paths, ["/z/track10.wav", "/a/track2.wav"]);
+
+        let mut paths = original.clone();
+        sort_paths(&mut paths, SortMode::AsSelected);
+        assert_eq!(paths, original);
+    }
+
+    #[test]
+    fn inspect_pickle_accepts_well_formed_streams() {
+        assert_eq!(inspect_pickle(b"\x80\x02}q", Some(b'.'), 20), Ok(Some(2)));
+        assert_eq!(inspect_pickle(b"(dp0", Some(b'.'), 20), Ok(None));
+
+        let mut framed = vec![0x80, 4, 0x95];
+        framed.extend_from_slice(&9u64.to_le_bytes());
+        assert_eq!(inspect_pickle(&framed, Some(b'.'), 20), Ok(Some(4)));
+    }
+
+    #[test]
+    fn inspect_pickle_rejects_bad_framing() {
+        assert!(inspect_pickle(b"", None, 0).is_err());
+        assert!(inspect_pickle(b"\x80", Some(b'.'), 1).is_err());
+        assert!(inspect_pickle(b"\x80\x06}q", Some(b'.'), 20).is_err());
+        assert!(inspect_pickle(b"PK\x03\x04", Some(b'.'), 20).is_err());
+        // Missing STOP
+        assert!(inspect_pickle(b"\x80\x02}q", Some(b'q'), 20).is_err());
+        // Frame header cut short, and a frame longer than the file
+        assert!(inspect_pickle(b"\x80\x04\x95\x01\x02", Some(b'.'), 5).is_err());
+        let mut framed = vec![0x80, 4, 0x95];
+        framed.extend_from_slice(&100u64.to_le_bytes());
+        assert!(inspect_pickle(&framed, Some(b'.'), 20).is_err());
+    }
+
+    #[test]
+    fn detect_model_format_reads_magic_bytes() {
+        assert_eq!(detect_model_format(b"PK\x03\x04rest", "pt"), Some("torch"));
+        assert_eq!(detect_model_format(b"\x80\x02}q", "pth"), Some("pickle"));
+        assert_eq!(detect_model_format(b"\x1c\0\0\0TFL3", "tflite"), Some("tflite"));
+        assert_eq!(detect_model_format(b"\x08\x07\x12", "onnx"), Some("onnx"));
+    }
+
+    #[test]
+    fn detect_model_format_rejects_unknown_contents() {
+        assert_eq!(detect_model_format(b"", "pt"), None);
+        assert_eq!(detect_model_format(b"\x80\x06}q", "pkl"), None);
+        // Without a magic number, ONNX is only recognised by its extension
+        assert_eq!(detect_model_format(b"\x08\x07\x12", "pt"), None);
+        assert_eq!(detect_model_format(b"<!DOCTYPE html>", "pt"), None);
+    }
+
+    fn write_project(name: &str, roots: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("dipper-test-{}-{}.json", std::process::id(), name));
+        fs::write(&path, format!(r#"{{"roots": {}}}"#, roots)).unwrap();
+        path
+    }
+
+    fn resolve(project: &Path, tagged: &str) -> Result<String, String> {
+        tauri::async_runtime::block_on(resolve_root_path(project.to_string_lossy().to_string(), tagged.to_string()))
+    }
+
+    #[test]
+    fn resolve_root_path_joins_relative_parts() {
+        let project = write_project("resolve", r#"{"data": "/mnt/data"}"#);
+        let expected = |parts: &[&str]| {
+            parts.iter().fold(PathBuf::from("/mnt/data"), |path, part| path.join(part)).to_string_lossy().to_string()
+        };
+
+        assert_eq!(resolve(&project, "@data/site 1/a.wav"), Ok(expected(&["site 1", "a.wav"])));
+        assert_eq!(resolve(&project, "@data\\site 1\\a.wav"), Ok(expected(&["site 1", "a.wav"])));
+        assert_eq!(resolve(&project, "@data/./site 1//a.wav"), Ok(expected(&["site 1", "a.wav"])));
+        assert_eq!(resolve(&project, "@data"), Ok(expected(&[])));
+        assert_eq!(resolve(&project, "/plain/a.wav"), Ok("/plain/a.wav".to_string()));
+        let _ = fs::remove_file(project);
+    }
+
+    #[test]
+    fn resolve_root_path_rejects_escapes_and_unknown_roots() {
+        let project = write_project("reject", r#"{"data": "/mnt/data"}"#);
+
+        assert!(resolve(&project, "@data/../etc/passwd").is_err());
+        assert!(resolve(&project, "@data/site\\..\\..\\etc").is_err());
+        assert!(resolve(&project, "@other/a.wav").is_err());
+        let _ = fs::remove_file(project);
+    }
+
+    #[test]
+    fn format_utc_formats_civil_dates() {
+        let at = |secs: u64| format_utc(UNIX_EPOCH + Duration::from_secs(secs));
+
+        assert_eq!(at(0), "1970-01-01 00:00:00");
+        assert_eq!(at(951_868_799), "2000-02-29 23:59:59");
+        assert_eq!(at(1_735_648_496), "2024-12-31 12:34:56");
+        assert_eq!(at(4_107_542_400), "2100-03-01 00:00:00");
+        // Times before the epoch clamp to it
+        assert_eq!(format_utc(UNIX_EPOCH - Duration::from_secs(1)), "1970-01-01 00:00:00");
+    }
+
+    #[test]
+    fn write_atomically_leaves_unrelated_temp_files_alone() {
+        let dir = std::env::temp_dir().join(format!("dipper-test-{}-atomic", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("config.json");
+        let user_file = dir.join("config.json.tmp");
+        fs::write(&user_file, "keep me").unwrap();
+
+        write_atomically(&target, b"first").unwrap();
+        write_atomically(&target, b"second").unwrap();
+
+        assert_eq!(fs::read_to_string(&target).unwrap(), "second");
+        assert_eq!(fs::read_to_string(&user_file).unwrap(), "keep me");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
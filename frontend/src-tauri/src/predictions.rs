@@ -0,0 +1,185 @@
+//! Streaming reader for wide-format prediction CSVs (file, start_time, end_time, <class scores>)
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// One row of a predictions CSV; `scores` is aligned with `Predictions::classes`
+pub struct PredictionRow {
+    pub file: String,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub scores: Vec<f32>,
+}
+
+impl PredictionRow {
+    /// Highest score in the row, or NaN when there are no class columns
+    pub fn max_score(&self) -> f32 {
+        self.scores.iter().copied().fold(f32::NAN, f32::max)
+    }
+
+    /// Index and score of the highest-scoring class, ignoring non-numeric cells
+    pub fn top_class(&self) -> Option<(usize, f32)> {
+        self.scores.iter().copied().enumerate()
+            .filter(|(_, score)| !score.is_nan())
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+}
+
+/// Row iterator over a predictions CSV that never holds the whole file in memory
+pub struct Predictions {
+    reader: csv::Reader<File>,
+    pub classes: Vec<String>,
+    file_idx: usize,
+    start_idx: usize,
+    end_idx: usize,
+    class_idx: Vec<usize>,
+}
+
+/// Open a predictions CSV and locate its standard and class columns
+pub fn open(path: &Path) -> Result<Predictions, String> {
+    let mut reader = csv::Reader::from_path(path)
+        .map_err(|e| format!("Failed to open predictions CSV: {}", e))?;
+    let headers = reader.headers()
+        .map_err(|e| format!("Failed to read CSV header: {}", e))?
+        .clone();
+
+    let find = |name: &str| {
+        headers.iter().position(|h| h == name)
+            .ok_or_else(|| format!("Predictions CSV is missing the '{}' column", name))
+    };
+    let file_idx = find("file")?;
+    let start_idx = find("start_time")?;
+    let end_idx = find("end_time")?;
+
+    let mut classes = Vec::new();
+    let mut class_idx = Vec::new();
+    for (i, name) in headers.iter().enumerate() {
+        if i != file_idx && i != start_idx && i != end_idx {
+            classes.push(name.to_string());
+            class_idx.push(i);
+        }
+    }
+
+    Ok(Predictions { reader, classes, file_idx, start_idx, end_idx, class_idx })
+}
+
+impl Iterator for Predictions {
+    type Item = Result<PredictionRow, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = csv::StringRecord::new();
+        match self.reader.read_record(&mut record) {
+            Ok(false) => None,
+            Err(e) => Some(Err(format!("Failed to read CSV row: {}", e))),
+            Ok(true) => {
+                let field = |i: usize| record.get(i).unwrap_or("");
+                let parse_time = |i: usize| field(i).trim().parse::<f64>()
+                    .map_err(|_| format!("Invalid time value '{}'", field(i)));
+                let start_time = match parse_time(self.start_idx) {
+                    Ok(v) => v,
+                    Err(e) => return Some(Err(e)),
+                };
+                let end_time = match parse_time(self.end_idx) {
+                    Ok(v) => v,
+                    Err(e) => return Some(Err(e)),
+                };
+                // Non-numeric cells (e.g. extra metadata columns) become NaN and never pass a threshold
+                let scores = self.class_idx.iter()
+                    .map(|&i| field(i).trim().parse::<f32>().unwrap_or(f32::NAN))
+                    .collect();
+                Some(Ok(PredictionRow {
+                    file: field(self.file_idx).to_string(),
+                    start_time,
+                    end_time,
+                    scores,
+                }))
+            }
+        }
+    }
+}
+
+// Files up to this size get an exact row count; larger ones are sampled
+const EXACT_COUNT_LIMIT: u64 = 256 * 1024 * 1024;
+const CHUNK_SIZE: usize = 1024 * 1024;
+// Evenly spaced chunks read to estimate the line length of a large file
+const SAMPLE_CHUNKS: u64 = 8;
+
+/// Number of data rows (lines after the header) in a CSV, counted by scanning for
+/// newlines in large chunks. Files over `EXACT_COUNT_LIMIT` are estimated from the
+/// average line length of sampled chunks. Quoted fields containing newlines are
+/// counted as extra rows.
+pub fn estimate_rows(path: &Path) -> Result<usize, String> {
+    let mut file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let size = file.metadata().map_err(|e| format!("Failed to read file: {}", e))?.len();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let count_newlines = |bytes: &[u8]| bytes.iter().filter(|&&b| b == b'\n').count();
+
+    if size <= EXACT_COUNT_LIMIT {
+        let mut lines = 0;
+        let mut last = b'\n';
+        loop {
+            let n = file.read(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
+            if n == 0 {
+                break;
+            }
+            lines += count_newlines(&buffer[..n]);
+            last = buffer[n - 1];
+        }
+        // A final line without a trailing newline still counts
+        if last != b'\n' {
+            lines += 1;
+        }
+        return Ok(lines.saturating_sub(1));
+    }
+
+    let n = file.read(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
+    let header_len = buffer[..n].iter().position(|&b| b == b'\n').map_or(n, |i| i + 1) as u64;
+
+    let body = size - header_len;
+    let (mut sampled_bytes, mut sampled_lines) = (0u64, 0u64);
+    for i in 0..SAMPLE_CHUNKS {
+        let offset = header_len + (body - CHUNK_SIZE as u64) * i / (SAMPLE_CHUNKS - 1);
+        file.seek(SeekFrom::Start(offset)).map_err(|e| format!("Failed to read file: {}", e))?;
+        file.read_exact(&mut buffer).map_err(|e| format!("Failed to read file: {}", e))?;
+        sampled_bytes += CHUNK_SIZE as u64;
+        sampled_lines += count_newlines(&buffer) as u64;
+    }
+    if sampled_lines == 0 {
+        return Ok(1);
+    }
+    Ok((body as f64 * sampled_lines as f64 / sampled_bytes as f64).round() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn write_csv(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("dipper-test-{}-{}.csv", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn rows(name: &str, contents: &str) -> usize {
+        let path = write_csv(name, contents);
+        let count = estimate_rows(&path).unwrap();
+        let _ = std::fs::remove_file(path);
+        count
+    }
+
+    #[test]
+    fn estimate_rows_counts_small_files_exactly() {
+        assert_eq!(rows("trailing", "file,start_time,end_time\na.wav,0,3\nb.wav,3,6\n"), 2);
+        assert_eq!(rows("no-trailing", "file,start_time,end_time\na.wav,0,3\nb.wav,3,6"), 2);
+        assert_eq!(rows("header-only", "file,start_time,end_time\n"), 0);
+        assert_eq!(rows("empty", ""), 0);
+    }
+
+    #[test]
+    fn estimate_rows_reports_missing_files() {
+        let path = std::env::temp_dir().join(format!("dipper-test-{}-missing.csv", std::process::id()));
+        assert!(estimate_rows(&path).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Folder walking for audio discovery
+
+use crate::audio::AUDIO_EXTENSIONS;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Whether a path has one of the audio extensions (case-insensitive)
+pub fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.as_str()))
+}
+
+/// Collect audio files under `root`, sorted lexicographically.
+///
+/// With `follow_symlinks` false, symlinked files and folders are skipped. With it
+/// true, links are followed and reported by their real target path; a link that
+/// points back at one of its own ancestors is a cycle and fails the scan.
+pub fn collect_audio_files(root: &Path, recursive: bool, follow_symlinks: bool) -> Result<Vec<PathBuf>, String> {
+    resume(ScanCheckpoint::new(root, recursive, follow_symlinks)?, |_| {}, || true)
+        .map_err(|e| e.to_string())
+}
+
+/// Why `resume` stopped before finishing
+#[derive(Debug)]
+pub enum ScanError {
+    /// A folder couldn't be read; `save` was just given the checkpoint to retry from
+    Unreadable(String),
+    /// Resuming would fail the same way, e.g. on a symlink cycle
+    Failed(String),
+}
+
+impl std::fmt::Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanError::Unreadable(message) | ScanError::Failed(message) => f.write_str(message),
+        }
+    }
+}
+
+// How often a running scan hands its progress to `save`
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Progress of a folder scan, saved periodically so an interrupted scan (e.g. a
+/// network mount dropping out) can continue instead of starting over
+#[derive(Serialize, Deserialize)]
+pub struct ScanCheckpoint {
+    pub root: PathBuf,
+    pub recursive: bool,
+    pub follow_symlinks: bool,
+    /// Folders still to read, each with the real paths of itself and its ancestors
+    pending: Vec<(PathBuf, Vec<PathBuf>)>,
+    /// Real paths of every folder reached so far
+    visited: HashSet<PathBuf>,
+    files: Vec<PathBuf>,
+}
+
+impl ScanCheckpoint {
+    /// A scan of `root` that hasn't read anything yet
+    pub fn new(root: &Path, recursive: bool, follow_symlinks: bool) -> Result<Self, String> {
+        let root_real = fs::canonicalize(root)
+            .map_err(|e| format!("Failed to read folder {}: {}", root.display(), e))?;
+        Ok(ScanCheckpoint {
+            root: root.to_path_buf(),
+            recursive,
+            follow_symlinks,
+            pending: vec![(root.to_path_buf(), vec![root_real.clone()])],
+            visited: HashSet::from([root_real]),
+            files: Vec::new(),
+        })
+    }
+}
+
+/// Continue a scan until every pending folder is read, returning its audio files
+/// sorted. `save` receives the checkpoint every `CHECKPOINT_INTERVAL` and when a
+/// folder can't be read; that folder stays pending, so resuming retries it.
+/// `on_folder` is called after each folder; returning false stops the scan early,
+/// saving the checkpoint and returning the files found so far.
+pub fn resume(
+    mut checkpoint: ScanCheckpoint,
+    mut save: impl FnMut(&ScanCheckpoint),
+    mut on_folder: impl FnMut() -> bool,
+) -> Result<Vec<PathBuf>, ScanError> {
+    let mut last_save = Instant::now();
+    while let Some((dir, ancestors)) = checkpoint.pending.last().cloned() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                save(&checkpoint);
+                return Err(ScanError::Unreadable(format!("Failed to read folder {}: {}", dir.display(), e)));
+            }
+        };
+        checkpoint.pending.pop();
+        read_entries(&mut checkpoint, entries, &ancestors).map_err(ScanError::Failed)?;
+
+        if !on_folder() {
+            save(&checkpoint);
+            break;
+        }
+        if last_save.elapsed() >= CHECKPOINT_INTERVAL {
+            save(&checkpoint);
+            last_save = Instant::now();
+        }
+    }
+
+    let mut files = checkpoint.files;
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn read_entries(checkpoint: &mut ScanCheckpoint, entries: fs::ReadDir, ancestors: &[PathBuf]) -> Result<(), String> {
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(link_meta) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+
+        let (path, is_dir) = if link_meta.file_type().is_symlink() {
+            if !checkpoint.follow_symlinks {
+                continue;
+            }
+            // Broken links are skipped like unreadable entries
+            let Ok(target) = fs::canonicalize(&path) else {
+                continue;
+            };
+            let is_dir = target.is_dir();
+            (target, is_dir)
+        } else {
+            (path, link_meta.is_dir())
+        };
+
+        if is_dir {
+            if !checkpoint.recursive {
+                continue;
+            }
+            let real = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+            if ancestors.contains(&real) {
+                return Err(format!("Symlink cycle detected at {}", entry.path().display()));
+            }
+            // Reached again through a different link: already scanned
+            if !checkpoint.visited.insert(real.clone()) {
+                continue;
+            }
+            let mut chain = ancestors.to_vec();
+            chain.push(real);
+            checkpoint.pending.push((path, chain));
+        } else if is_audio_file(&path) {
+            checkpoint.files.push(path);
+        }
+    }
+    Ok(())
+}
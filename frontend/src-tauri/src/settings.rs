@@ -0,0 +1,68 @@
+//! Persistent app preferences stored as a JSON object in the app config dir
+
+use crate::AppDirs;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::Manager;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+// Serializes read-modify-write cycles on the settings file
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+fn settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map(|dir| dir.join(SETTINGS_FILE))
+        .map_err(|e| format!("Failed to resolve app config dir: {}", e))
+}
+
+fn load(app: &tauri::AppHandle) -> serde_json::Map<String, serde_json::Value> {
+    settings_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Read a setting, returning `None` when it is unset or has an unexpected type
+pub fn get<T: DeserializeOwned>(app: &tauri::AppHandle, key: &str) -> Option<T> {
+    let _guard = SETTINGS_LOCK.lock().unwrap();
+    load(app)
+        .get(key)
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+}
+
+/// Store a setting, creating the settings file on first write
+pub fn set<T: Serialize>(app: &tauri::AppHandle, key: &str, value: T) -> Result<(), String> {
+    let value = serde_json::to_value(value)
+        .map_err(|e| format!("Failed to serialize setting: {}", e))?;
+
+    if let Some(error) = app.try_state::<AppDirs>().and_then(|dirs| dirs.error.clone()) {
+        return Err(format!("Settings can't be saved: {}", error));
+    }
+
+    let _guard = SETTINGS_LOCK.lock().unwrap();
+    let mut settings = load(app);
+    settings.insert(key.to_string(), value);
+
+    let path = settings_path(app)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    // Write to a sibling temp file and rename so a crash never leaves a truncated file
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, content)
+        .and_then(|_| fs::rename(&temp_path, &path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&temp_path);
+            format!("Failed to save settings: {}", e)
+        })
+}
@@ -0,0 +1,143 @@
+//! Keyboard shortcuts for common actions, implemented as accelerators on an
+//! "Actions" menu so they only fire while Dipper is focused. Bindings are kept in
+//! settings so they can be changed for conflicting keyboard layouts.
+
+use crate::settings;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use tauri::menu::{Menu, MenuEvent, MenuItem, Submenu};
+use tauri::{Emitter, Manager};
+
+const SHORTCUTS_KEY: &str = "shortcuts";
+
+/// Rebindable action: id, menu label, default accelerator, and the event emitted
+/// to the main window, whose handler runs the matching command
+struct Action {
+    id: &'static str,
+    label: &'static str,
+    default: &'static str,
+    event: &'static str,
+}
+
+const ACTIONS: &[Action] = &[
+    Action { id: "open_files", label: "Open Files...", default: "CmdOrCtrl+O", event: "shortcut-open-files" },
+    Action { id: "open_folder", label: "Open Folder...", default: "CmdOrCtrl+Shift+O", event: "shortcut-open-folder" },
+    Action { id: "save", label: "Save...", default: "CmdOrCtrl+S", event: "shortcut-save" },
+];
+
+// Menu items by action id, so a rebind updates the live accelerator
+pub struct ShortcutState {
+    items: Mutex<HashMap<&'static str, MenuItem<tauri::Wry>>>,
+}
+
+fn saved_bindings(app: &tauri::AppHandle) -> HashMap<String, String> {
+    settings::get(app, SHORTCUTS_KEY).unwrap_or_default()
+}
+
+/// Current accelerator for every action, saved or default
+pub fn bindings(app: &tauri::AppHandle) -> BTreeMap<String, String> {
+    let saved = saved_bindings(app);
+    ACTIONS.iter()
+        .map(|action| {
+            let accelerator = saved.get(action.id).map_or(action.default, String::as_str);
+            (action.id.to_string(), accelerator.to_string())
+        })
+        .collect()
+}
+
+/// Attach the Actions menu: app-wide on macOS, added to the default menu there since
+/// the standard Edit shortcuts (copy, paste, ...) only work through it; elsewhere as
+/// the main window's only menu, so the splash stays bare. A saved binding that no
+/// longer parses falls back to the default.
+pub fn install(app: &tauri::AppHandle, main_window: &tauri::WebviewWindow) -> tauri::Result<ShortcutState> {
+    let saved = saved_bindings(app);
+    let mut items = HashMap::new();
+    for action in ACTIONS {
+        let item = match saved.get(action.id) {
+            Some(accelerator) => MenuItem::with_id(app, action.id, action.label, true, Some(accelerator))
+                .or_else(|e| {
+                    app_eprintln!("Invalid shortcut {:?} for {}: {} (using {})", accelerator, action.id, e, action.default);
+                    MenuItem::with_id(app, action.id, action.label, true, Some(action.default))
+                })?,
+            None => MenuItem::with_id(app, action.id, action.label, true, Some(action.default))?,
+        };
+        items.insert(action.id, item);
+    }
+
+    let entries: Vec<&dyn tauri::menu::IsMenuItem<tauri::Wry>> = ACTIONS.iter()
+        .map(|action| &items[action.id] as &dyn tauri::menu::IsMenuItem<tauri::Wry>)
+        .collect();
+    let actions = Submenu::with_items(app, "Actions", true, &entries)?;
+
+    #[cfg(target_os = "macos")]
+    {
+        let _ = main_window;
+        let menu = Menu::default(app)?;
+        menu.append(&actions)?;
+        app.set_menu(menu)?;
+    }
+    #[cfg(not(target_os = "macos"))]
+    main_window.set_menu(Menu::with_items(app, &[&actions])?)?;
+
+    Ok(ShortcutState { items: Mutex::new(items) })
+}
+
+/// Forward an Actions menu click or accelerator to the main window
+pub fn handle_menu_event(app: &tauri::AppHandle, event: &MenuEvent) {
+    if let Some(action) = ACTIONS.iter().find(|action| event.id() == action.id) {
+        let _ = app.emit_to("main", action.event, ());
+    }
+}
+
+/// Key combination in a comparable form: case-insensitive, with modifier aliases
+/// (including what `CmdOrCtrl` means on this platform) folded together and
+/// modifiers in a fixed order
+fn normalize_accelerator(accelerator: &str) -> String {
+    let cmd_or_ctrl = if cfg!(target_os = "macos") { "super" } else { "ctrl" };
+    let mut keys: Vec<String> = accelerator.split('+')
+        .map(|key| match key.trim().to_lowercase().as_str() {
+            "cmdorctrl" | "cmdorcontrol" | "commandorctrl" | "commandorcontrol" => cmd_or_ctrl.to_string(),
+            "cmd" | "command" | "meta" => "super".to_string(),
+            "control" => "ctrl".to_string(),
+            "option" => "alt".to_string(),
+            key => key.to_string(),
+        })
+        .collect();
+    // The last key is the main key; everything before it is a modifier
+    let main = keys.pop().unwrap_or_default();
+    keys.sort();
+    keys.push(main);
+    keys.join("+")
+}
+
+/// Bind `action` to `accelerator` (e.g. "CmdOrCtrl+Alt+O"), or back to its
+/// default when `None`. The menu is updated immediately and the choice is saved.
+/// A combination already bound to another action is rejected.
+pub fn rebind(app: &tauri::AppHandle, action_id: &str, accelerator: Option<String>) -> Result<(), String> {
+    let action = ACTIONS.iter()
+        .find(|action| action.id == action_id)
+        .ok_or_else(|| format!("Unknown shortcut action: {}", action_id))?;
+    let wanted = normalize_accelerator(accelerator.as_deref().unwrap_or(action.default));
+    let current = bindings(app);
+    if let Some((other, _)) = current.iter()
+        .find(|(id, bound)| id.as_str() != action.id && normalize_accelerator(bound) == wanted)
+    {
+        return Err(format!(
+            "{} is already the shortcut for {}",
+            accelerator.as_deref().unwrap_or(action.default),
+            other
+        ));
+    }
+    let state = app.try_state::<ShortcutState>();
+    if let Some(item) = state.as_ref().and_then(|state| state.items.lock().unwrap().get(action.id).cloned()) {
+        item.set_accelerator(Some(accelerator.as_deref().unwrap_or(action.default)))
+            .map_err(|e| format!("Invalid shortcut {:?}: {}", accelerator.as_deref().unwrap_or_default(), e))?;
+    }
+
+    let mut saved = saved_bindings(app);
+    match accelerator {
+        Some(accelerator) => saved.insert(action.id.to_string(), accelerator),
+        None => saved.remove(action.id),
+    };
+    settings::set(app, SHORTCUTS_KEY, saved)
+}
@@ -0,0 +1,83 @@
+//! Host information used for diagnostics and resource checks
+
+use std::path::Path;
+use sysinfo::{Disks, Pid, ProcessesToUpdate, System};
+
+/// Available and total bytes on the filesystem containing `path`, chosen as the
+/// disk with the longest mount point that prefixes the (canonical) path
+pub fn disk_space(path: &Path) -> Option<(u64, u64)> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let disks = Disks::new_with_refreshed_list();
+    disks.list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().components().count())
+        .map(|disk| (disk.available_space(), disk.total_space()))
+}
+
+/// Total and available physical memory in bytes
+pub fn memory() -> (u64, u64) {
+    let mut system = System::new();
+    system.refresh_memory();
+    (system.total_memory(), system.available_memory())
+}
+
+/// Replace the user's home directory prefix with `~` so shared diagnostics
+/// don't reveal the username
+pub fn redact_home(path: &str) -> String {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"));
+    let Some(home) = home.ok().filter(|home| !home.is_empty()) else {
+        return path.to_string();
+    };
+    // Compared by whole components, so /home/al doesn't match /home/alice
+    match Path::new(path).strip_prefix(&home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => Path::new("~").join(rest).to_string_lossy().to_string(),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// Name of the first NVIDIA GPU, if `nvidia-smi` is available
+pub fn gpu_name() -> Option<String> {
+    let output = std::process::Command::new("nvidia-smi")
+        .args(["--query-gpu=name", "--format=csv,noheader"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Combined CPU percent and resident memory (bytes) of a process and all of its
+/// descendants. Frozen sidecars run the real server as a child of a small
+/// bootloader, so the root process alone would under-report.
+pub fn process_tree_usage(pid: u32) -> Option<(f32, u64)> {
+    let mut system = System::new();
+    system.refresh_processes(ProcessesToUpdate::All, true);
+    // CPU usage is a delta between two refreshes
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.max(std::time::Duration::from_millis(200)));
+    system.refresh_processes(ProcessesToUpdate::All, true);
+
+    let root = Pid::from_u32(pid);
+    system.process(root)?;
+    let processes = system.processes();
+    let in_tree = |mut current: Pid| loop {
+        if current == root {
+            return true;
+        }
+        match processes.get(&current).and_then(|p| p.parent()) {
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    };
+
+    let (cpu, memory) = processes.iter()
+        .filter(|(pid, _)| in_tree(**pid))
+        .fold((0.0, 0), |(cpu, memory), (_, process)| (cpu + process.cpu_usage(), memory + process.memory()));
+    Some((cpu, memory))
+}
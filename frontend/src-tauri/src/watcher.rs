@@ -0,0 +1,212 @@
+//! Folder watching with burst coalescing for bulk copies
+
+use crate::scan;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// Thresholds for switching a watcher into bulk mode
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct BurstConfig {
+    /// Number of events within `window_ms` that counts as a burst
+    pub threshold: usize,
+    pub window_ms: u64,
+    /// How long the folder must be quiet before a burst is considered over
+    pub quiet_ms: u64,
+}
+
+impl Default for BurstConfig {
+    fn default() -> Self {
+        BurstConfig { threshold: 50, window_ms: 1000, quiet_ms: 2000 }
+    }
+}
+
+/// Payload for the per-file `folder-changed` event
+#[derive(Clone, Serialize)]
+struct FolderChange {
+    folder: String,
+    path: String,
+    kind: &'static str,
+}
+
+/// Payload for the coalesced `folder-bulk-changed` event
+#[derive(Clone, Serialize, Default)]
+struct BulkChange {
+    folder: String,
+    created: usize,
+    modified: usize,
+    removed: usize,
+}
+
+/// Start watching `folder` recursively. Events are forwarded to the frontend
+/// until the returned watcher is dropped.
+pub fn start(app: tauri::AppHandle, folder: &str, config: BurstConfig) -> Result<notify::RecommendedWatcher, String> {
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| format!("Failed to create folder watcher: {}", e))?;
+    watcher.watch(Path::new(folder), RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch folder: {}", e))?;
+
+    let folder = folder.to_string();
+    thread::spawn(move || forward_events(app, folder, config, rx));
+    Ok(watcher)
+}
+
+/// Payload for `new-audio-file`, sent once a new recording has finished writing
+#[derive(Clone, Serialize)]
+struct NewAudioFile {
+    folder: String,
+    path: String,
+}
+
+// How often the size of a new audio file is rechecked; it counts as complete once
+// two checks in a row see the same size
+const SIZE_CHECK_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// A newly created audio file that may still be growing
+struct PendingFile {
+    size: Option<u64>,
+    checked: Instant,
+}
+
+/// Emit granular events normally, switching to a single aggregate event per
+/// burst so a card offload doesn't flood the webview. New audio files are also
+/// tracked until their size settles and then announced with `new-audio-file`.
+/// Exits once the watcher (and with it the channel sender) is dropped.
+fn forward_events(
+    app: tauri::AppHandle,
+    folder: String,
+    config: BurstConfig,
+    rx: mpsc::Receiver<notify::Result<notify::Event>>,
+) {
+    let window = Duration::from_millis(config.window_ms);
+    let quiet = Duration::from_millis(config.quiet_ms);
+    let mut recent: VecDeque<Instant> = VecDeque::new();
+    let mut bulk: Option<BulkChange> = None;
+    let mut last_event = Instant::now();
+    let mut pending: HashMap<PathBuf, PendingFile> = HashMap::new();
+
+    loop {
+        let timeout = if pending.is_empty() { quiet } else { SIZE_CHECK_INTERVAL.min(quiet) };
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(event)) => {
+                last_event = Instant::now();
+                track_new_audio(&event, &mut pending);
+                record_event(&app, &folder, &config, window, event, &mut recent, &mut bulk);
+            }
+            Ok(Err(e)) => app_eprintln!("Folder watcher error: {}", e),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        if bulk.is_some() && last_event.elapsed() >= quiet {
+            if let Some(summary) = bulk.take() {
+                let _ = app.emit("folder-bulk-changed", summary);
+                recent.clear();
+            }
+        }
+        emit_finished_audio(&app, &folder, &mut pending);
+    }
+}
+
+/// Forward one event as `folder-changed`, or add it to the running burst summary
+fn record_event(
+    app: &tauri::AppHandle,
+    folder: &str,
+    config: &BurstConfig,
+    window: Duration,
+    event: notify::Event,
+    recent: &mut VecDeque<Instant>,
+    bulk: &mut Option<BulkChange>,
+) {
+    let kind = match event.kind {
+        EventKind::Create(_) => "created",
+        EventKind::Modify(_) => "modified",
+        EventKind::Remove(_) => "removed",
+        _ => return,
+    };
+
+    let now = Instant::now();
+    for _ in &event.paths {
+        recent.push_back(now);
+    }
+    while recent.front().is_some_and(|t| now.duration_since(*t) > window) {
+        recent.pop_front();
+    }
+    if bulk.is_none() && recent.len() >= config.threshold {
+        *bulk = Some(BulkChange { folder: folder.to_string(), ..Default::default() });
+    }
+
+    match bulk.as_mut() {
+        Some(summary) => {
+            let count = event.paths.len();
+            match kind {
+                "created" => summary.created += count,
+                "modified" => summary.modified += count,
+                _ => summary.removed += count,
+            }
+        }
+        None => {
+            for path in &event.paths {
+                let _ = app.emit("folder-changed", FolderChange {
+                    folder: folder.to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    kind,
+                });
+            }
+        }
+    }
+}
+
+/// Start tracking audio files that were created or moved into the folder, and stop
+/// tracking ones that were removed before they settled
+fn track_new_audio(event: &notify::Event, pending: &mut HashMap<PathBuf, PendingFile>) {
+    let arrived = match event.kind {
+        EventKind::Create(_) => event.paths.as_slice(),
+        // A rename reports the destination last
+        EventKind::Modify(ModifyKind::Name(RenameMode::To | RenameMode::Both)) => {
+            event.paths.last().map(std::slice::from_ref).unwrap_or_default()
+        }
+        EventKind::Remove(_) => {
+            for path in &event.paths {
+                pending.remove(path);
+            }
+            return;
+        }
+        _ => return,
+    };
+    for path in arrived.iter().filter(|path| scan::is_audio_file(path)) {
+        pending.entry(path.clone()).or_insert(PendingFile { size: None, checked: Instant::now() });
+    }
+}
+
+/// Recheck the size of pending files and emit `new-audio-file` for those that
+/// stopped growing. Files that vanished are dropped.
+fn emit_finished_audio(app: &tauri::AppHandle, folder: &str, pending: &mut HashMap<PathBuf, PendingFile>) {
+    pending.retain(|path, file| {
+        if file.checked.elapsed() < SIZE_CHECK_INTERVAL {
+            return true;
+        }
+        let Ok(size) = fs::metadata(path).map(|meta| meta.len()) else {
+            return false;
+        };
+        if file.size == Some(size) {
+            let _ = app.emit("new-audio-file", NewAudioFile {
+                folder: folder.to_string(),
+                path: path.to_string_lossy().to_string(),
+            });
+            return false;
+        }
+        file.size = Some(size);
+        file.checked = Instant::now();
+        true
+    });
+}
@@ -0,0 +1,97 @@
+//! Remembers the main window's size and position between launches
+
+use crate::settings;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Manager, PhysicalPosition, PhysicalSize};
+
+const GEOMETRY_KEY: &str = "main_window_geometry";
+
+// Moves and resizes arrive as a stream of events; save once they settle
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// Time of the most recent move/resize; `Some` while a save is scheduled
+static LAST_CHANGE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Outer position and inner size in physical pixels
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct Geometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+/// Apply the saved geometry to the main window, pulled back onto a connected monitor
+/// if it would otherwise open off-screen
+pub fn restore(window: &tauri::WebviewWindow) {
+    let Some(saved) = settings::get::<Geometry>(window.app_handle(), GEOMETRY_KEY) else {
+        return;
+    };
+    let monitors = window.available_monitors().unwrap_or_default();
+    let overlaps = |monitor: &tauri::Monitor| {
+        let (pos, size) = (monitor.position(), monitor.size());
+        saved.x < pos.x + size.width as i32
+            && saved.x + saved.width as i32 > pos.x
+            && saved.y < pos.y + size.height as i32
+            && saved.y + saved.height as i32 > pos.y
+    };
+    let monitor = match monitors.iter().find(|m| overlaps(m)) {
+        Some(monitor) => monitor.clone(),
+        None => match window.primary_monitor().ok().flatten().or_else(|| monitors.first().cloned()) {
+            Some(monitor) => monitor,
+            None => return,
+        },
+    };
+
+    let (pos, size) = (monitor.position(), monitor.size());
+    let width = saved.width.min(size.width);
+    let height = saved.height.min(size.height);
+    let x = saved.x.clamp(pos.x, pos.x + (size.width - width) as i32);
+    let y = saved.y.clamp(pos.y, pos.y + (size.height - height) as i32);
+
+    let _ = window.set_size(PhysicalSize::new(width, height));
+    let _ = window.set_position(PhysicalPosition::new(x, y));
+}
+
+/// Record a move/resize of the main window; the geometry is written once the
+/// window has been still for `SAVE_DEBOUNCE`
+pub fn schedule_save(app: &tauri::AppHandle) {
+    let already_scheduled = LAST_CHANGE.lock().unwrap().replace(Instant::now()).is_some();
+    if already_scheduled {
+        return;
+    }
+
+    let app = app.clone();
+    thread::spawn(move || {
+        loop {
+            let last = LAST_CHANGE.lock().unwrap().expect("save scheduled");
+            let elapsed = last.elapsed();
+            if elapsed >= SAVE_DEBOUNCE {
+                break;
+            }
+            thread::sleep(SAVE_DEBOUNCE - elapsed);
+        }
+        *LAST_CHANGE.lock().unwrap() = None;
+        save(&app);
+    });
+}
+
+fn save(app: &tauri::AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    // Minimized windows report placeholder coordinates (e.g. -32000 on Windows)
+    if window.is_minimized().unwrap_or(false) {
+        return;
+    }
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+        return;
+    };
+    let geometry = Geometry { x: position.x, y: position.y, width: size.width, height: size.height };
+    if let Err(e) = settings::set(app, GEOMETRY_KEY, geometry) {
+        app_eprintln!("Failed to save window geometry: {}", e);
+    }
+}
@@ -20,6 +20,7 @@ import ColorizeIcon from '@mui/icons-material/Colorize';
 import SettingsIcon from '@mui/icons-material/Settings';
 import ListAltIcon from '@mui/icons-material/PlaylistPlay'
 import BubbleChartIcon from '@mui/icons-material/BubbleChart';
+import Alert from '@mui/material/Alert';
 import './App.css';
 import SongSpaceTab from './components/SongSpaceTab';
 import ReviewTab from './components/ReviewTab';
@@ -30,6 +31,7 @@ import TrainingTaskCreationForm from './components/TrainingTaskCreationForm';
 import ExtractionTaskCreationForm from './components/ExtractionTaskCreationForm';
 import TaskMonitor from './components/TaskMonitor';
 import taskManager from './utils/TaskManager';
+import { getStorageError, onStorageUnavailable } from './utils/fileOperations';
 import { useBackendUrl } from './hooks/useBackendUrl';
 import { useDarkMode } from './hooks/useDarkMode';
 
@@ -119,6 +121,9 @@ function App() {
   const [showEnvDialog, setShowEnvDialog] = useState(false);
   const envPollRef = React.useRef(null);
 
+  // Set when the app's config/data folders couldn't be created
+  const [storageError, setStorageError] = useState(null);
+
   const tabs = [
     { id: 'inference',  name: 'Inference',   icon: <PlayArrowIcon /> },
     ENABLE_TRAINING  && { id: 'training',  name: 'Training',    icon: <SchoolIcon /> },
@@ -180,6 +185,18 @@ function App() {
     };
   }, []);
 
+  // Storage problems found at startup: announced once the page has loaded, and
+  // read directly in case the event fired before the listener was attached
+  useEffect(() => {
+    const unlisten = onStorageUnavailable(error => setStorageError(error));
+    getStorageError()
+      .then(error => setStorageError(error))
+      .catch(() => { });
+    return () => {
+      unlisten.then(stop => stop()).catch(() => { });
+    };
+  }, []);
+
   // ML environment: check on launch, then show dialog if missing
   useEffect(() => {
     if (isReviewOnly || !backendUrl) return;
@@ -353,6 +370,12 @@ function App() {
         flexDirection: 'column',
         minHeight: '100vh'
       }}>
+        {storageError && (
+          <Alert severity="warning" onClose={() => setStorageError(null)} sx={{ mb: 2 }}>
+            Settings will not be saved: {storageError}
+          </Alert>
+        )}
+
         {/* ML environment install dialog */}
         {showEnvDialog && (
           <div className="env-dialog-overlay">
@@ -413,7 +413,7 @@ function ReviewTab({ drawerOpen = false, isReviewOnly = false, isActive = true }
 
       const filePath = await saveFile('review_config.json');
       if (filePath) {
-        const result = await writeFile(filePath, configJson);
+        const result = await writeFile(filePath, configJson, { overwrite: true });
         if (result.success) {
           console.log('Config saved to:', filePath);
           return true;
@@ -1327,7 +1327,7 @@ function ReviewTab({ drawerOpen = false, isReviewOnly = false, isActive = true }
       // Try to write error to a log file for debugging
       const errorLog = `Error loading annotation task at ${new Date().toISOString()}:\n${err.message}\n${err.stack}\n\n`;
       try {
-        await writeFile('/tmp/annotation_errors.log', errorLog);
+        await writeFile('/tmp/annotation_errors.log', errorLog, { overwrite: true });
       } catch (logErr) {
         console.error('Could not write to error log:', logErr);
       }
@@ -2469,7 +2469,7 @@ function ReviewTab({ drawerOpen = false, isReviewOnly = false, isActive = true }
 
       if (saveLocation) {
         const csvContent = exportToCSV(annotationData, settings);
-        await writeFile(saveLocation, csvContent);
+        await writeFile(saveLocation, csvContent, { overwrite: true });
         setHasUnsavedChanges(false);
         console.log('Auto-saved to:', saveLocation);
       }
@@ -2483,7 +2483,7 @@ function ReviewTab({ drawerOpen = false, isReviewOnly = false, isActive = true }
       // If we have a save path, use it directly
       if (currentSavePath) {
         const csvContent = exportToCSV(annotationData, settings);
-        await writeFile(currentSavePath, csvContent);
+        await writeFile(currentSavePath, csvContent, { overwrite: true });
         setHasUnsavedChanges(false);
         console.log('Saved to:', currentSavePath);
         return;
@@ -2504,7 +2504,7 @@ function ReviewTab({ drawerOpen = false, isReviewOnly = false, isActive = true }
 
     const filePath = await saveFile(defaultName);
     if (filePath) {
-      await writeFile(filePath, csvContent);
+      await writeFile(filePath, csvContent, { overwrite: true });
       setCurrentSavePath(filePath); // Set the save path for future auto-saves
       setHasUnsavedChanges(false);
     }
@@ -25,18 +25,35 @@ import { useDarkMode } from '../hooks/useDarkMode';
  * Currently supports:
  * - Max concurrent background tasks
  * - Extraction task exemption from concurrency limit
+ *
+ * Also shows the app and backend versions when running in Tauri
  */
 function SettingsTab() {
   const [maxConcurrentTasks, setMaxConcurrentTasks] = useState(1);
   const [exemptExtractionTasks, setExemptExtractionTasks] = useState(false);
   const [saveMessage, setSaveMessage] = useState(null);
+  const [versions, setVersions] = useState(null);
   const { darkMode, set: setDarkMode } = useDarkMode();
 
   // Load settings from localStorage on mount
   useEffect(() => {
     loadSettings();
+    loadVersions();
   }, []);
 
+  const loadVersions = async () => {
+    if (!window.__TAURI_INTERNALS__) {
+      return;
+    }
+    const { invoke } = await import('@tauri-apps/api/core');
+    const app = await invoke('get_app_version').catch(() => null);
+    const server = await invoke('get_server_version').catch((error) => {
+      console.error('Failed to get backend version:', error);
+      return null;
+    });
+    setVersions({ app, server });
+  };
+
   const loadSettings = () => {
     try {
       const savedSettings = localStorage.getItem('dipper_settings');
@@ -194,6 +211,23 @@ function SettingsTab() {
         </CardContent>
       </Card>
 
+      {versions && (
+        <Card sx={{ mb: 3 }}>
+          <CardContent>
+            <Typography variant="h6" gutterBottom>
+              About Dipper
+            </Typography>
+            <Divider sx={{ my: 2 }} />
+            <Typography variant="body2" color="text.secondary">
+              App version: {versions.app ?? 'unknown'}
+            </Typography>
+            <Typography variant="body2" color="text.secondary">
+              Backend version: {versions.server ?? 'unknown'}
+            </Typography>
+          </CardContent>
+        </Card>
+      )}
+
       <Card>
         <CardContent>
           <Typography variant="h6" gutterBottom>
@@ -114,7 +114,11 @@ function CreateInferenceTaskForm({ onTaskCreate, onTaskCreateAndRun }) {
 
   const handleFileSelection = async () => {
     try {
-      const files = await selectFiles();
+      const { accepted: files, rejected } = await selectFiles();
+      if (rejected.length > 0) {
+        const total = files.length + rejected.length;
+        alert(`${rejected.length} of ${total} selected files were unreadable and will be skipped:\n${rejected.join('\n')}`);
+      }
       if (files && files.length > 0) {
         setConfig(prev => ({
           ...prev,
@@ -40,20 +40,57 @@ async function invokeTauri(command, args = {}) {
   throw new Error('Tauri API not available');
 }
 
+/**
+ * Whether an error from a Tauri command is a dismissed dialog rather than a failure.
+ * Commands report errors as `{ kind, message }`; see src-tauri/src/error.rs.
+ */
+export const isCancelled = (error) => error?.kind === 'cancelled';
+
+/**
+ * Invoke a dialog command, resolving to `emptyValue` when the user cancels
+ * (matching server mode, where the pickers resolve empty on cancel)
+ */
+async function invokeDialog(command, args, emptyValue) {
+  try {
+    return await invokeTauri(command, args);
+  } catch (error) {
+    if (isCancelled(error)) {
+      return emptyValue;
+    }
+    throw error;
+  }
+}
+
 /**
  * Select multiple audio files
- * @returns {Promise<string[]>} Array of selected file paths
+ * @param {string[]} [extensions] - Extensions for the "Audio Files" filter (defaults to
+ *   wav, mp3, flac, ogg, m4a)
+ * @param {number} [maxFiles] - Return at most this many paths (no cap by default)
+ * @param {string} [primaryExtension] - Extension to offer as its own filter, selected
+ *   by default (e.g. 'flac')
+ * @param {string} [sort] - 'as_selected' (default), 'name', 'name_natural' (so
+ *   'file2' comes before 'file10') or 'modified' (oldest first); applied before maxFiles
+ * @returns {Promise<{accepted: string[], rejected: string[], truncated: boolean, total: number}>}
+ *   Readable file paths, any selected paths that could not be read, and whether the
+ *   selection of `total` files was cut down to `maxFiles`
  */
-export const selectFiles = async () => {
+export const selectFiles = async (extensions, maxFiles, primaryExtension, sort) => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('select_files');
+      return await invokeDialog('select_files', { extensions, maxFiles, primaryExtension, sort }, { accepted: [], rejected: [], truncated: false, total: 0 });
     }
     throw new Error('Local mode file selection not available');
   } else {
     // Server mode: Use SVAR file browser
     const result = await showAudioFilePicker(true);
-    return result || [];
+    const paths = result || [];
+    const truncated = maxFiles !== undefined && paths.length > maxFiles;
+    return {
+      accepted: truncated ? paths.slice(0, maxFiles) : paths,
+      rejected: [],
+      truncated,
+      total: paths.length
+    };
   }
 };
 
@@ -64,7 +101,7 @@ export const selectFiles = async () => {
 export const selectFolder = async () => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('select_folder');
+      return await invokeDialog('select_folder', {}, '');
     }
     throw new Error('Local mode folder selection not available');
   } else {
@@ -74,6 +111,23 @@ export const selectFolder = async () => {
   }
 };
 
+/**
+ * Select one or more folders
+ * @returns {Promise<string[]>} Selected folder paths
+ */
+export const selectFolders = async () => {
+  if (isLocalMode()) {
+    if (isTauriAvailable()) {
+      return await invokeDialog('select_folders', {}, []);
+    }
+    throw new Error('Local mode folder selection not available');
+  } else {
+    // Server mode: SVAR folder browser picks one folder at a time
+    const result = await showFolderPicker();
+    return result ? [result] : [];
+  }
+};
+
 /**
  * Select CSV or PKL prediction files
  * @returns {Promise<string[]>} Array of selected file paths
@@ -81,7 +135,7 @@ export const selectFolder = async () => {
 export const selectCSVFiles = async () => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('select_csv_files');
+      return await invokeDialog('select_csv_files', {}, []);
     }
     throw new Error('Local mode CSV file selection not available');
   } else {
@@ -98,7 +152,7 @@ export const selectCSVFiles = async () => {
 export const selectTextFiles = async () => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('select_text_files');
+      return await invokeDialog('select_text_files', {}, []);
     }
     throw new Error('Local mode text file selection not available');
   } else {
@@ -115,7 +169,7 @@ export const selectTextFiles = async () => {
 export const selectJSONFiles = async () => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('select_json_files');
+      return await invokeDialog('select_json_files', {}, []);
     }
     throw new Error('Local mode JSON file selection not available');
   } else {
@@ -132,7 +186,7 @@ export const selectJSONFiles = async () => {
 export const selectModelFiles = async () => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('select_model_files');
+      return await invokeDialog('select_model_files', {}, []);
     }
     throw new Error('Local mode model file selection not available');
   } else {
@@ -142,6 +196,23 @@ export const selectModelFiles = async () => {
   }
 };
 
+/**
+ * List the audio files in a folder, optionally including subfolders
+ * @param {string} folder - Folder to scan
+ * @param {boolean} recursive - Whether to descend into subfolders
+ * @returns {Promise<string[]>} Sorted audio file paths (empty if none found)
+ */
+export const scanFolderForAudio = async (folder, recursive = true) => {
+  if (isLocalMode()) {
+    if (isTauriAvailable()) {
+      return await invokeTauri('scan_folder_for_audio', { folder, recursive });
+    }
+    throw new Error('Local mode folder scanning not available');
+  } else {
+    throw new Error('Folder scanning is not available in server mode');
+  }
+};
+
 /**
  * Generate a unique folder name by appending numeric suffix if needed
  * @param {string} basePath - Base directory path
@@ -172,6 +243,23 @@ export const generateUniqueFolderName = async (basePath, folderName) => {
   }
 };
 
+/**
+ * Create a uniquely named folder, appending a numeric suffix if the name is taken
+ * @param {string} basePath - Base directory path
+ * @param {string} folderName - Desired folder name
+ * @returns {Promise<string>} Full path of the folder that was created
+ */
+export const createUniqueFolder = async (basePath, folderName) => {
+  if (isLocalMode()) {
+    if (isTauriAvailable()) {
+      return await invokeTauri('create_unique_folder', { basePath, folderName });
+    }
+    throw new Error('Local mode folder creation not available');
+  } else {
+    throw new Error('Folder creation is not available in server mode');
+  }
+};
+
 /**
  * Show save file dialog
  * @param {string} defaultName - Default file name
@@ -180,7 +268,7 @@ export const generateUniqueFolderName = async (basePath, folderName) => {
 export const saveFile = async (defaultName) => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('save_file', { defaultName });
+      return await invokeDialog('save_file', { defaultName }, '');
     }
     throw new Error('Local mode save file dialog not available');
   } else {
@@ -194,12 +282,16 @@ export const saveFile = async (defaultName) => {
  * Write content to a file
  * @param {string} filePath - File path to write to
  * @param {string} content - Content to write
+ * @param {Object} [options]
+ * @param {boolean} [options.overwrite=false] - Replace an existing file; when false an
+ *   existing file makes the call throw an error with kind 'already_exists'
+ * @param {boolean} [options.createParents=true] - Create missing parent folders first
  * @returns {Promise<{success: boolean, error?: string}>} Result of write operation
  */
-export const writeFile = async (filePath, content) => {
+export const writeFile = async (filePath, content, { overwrite = false, createParents = true } = {}) => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      await invokeTauri('write_file', { filePath, content });
+      await invokeTauri('write_file', { filePath, content, overwrite, createParents });
       return { success: true };
     }
     throw new Error('Local mode file write not available');
@@ -221,15 +313,55 @@ export const writeFile = async (filePath, content) => {
   }
 };
 
+/**
+ * Append content to a file, creating it if needed (desktop only)
+ * @param {string} filePath - File path to append to
+ * @param {string} content - Content to append
+ */
+export const appendFile = async (filePath, content) => {
+  if (isLocalMode()) {
+    if (isTauriAvailable()) {
+      await invokeTauri('append_file', { filePath, content });
+      return;
+    }
+    throw new Error('Local mode file append not available');
+  } else {
+    throw new Error('Appending to files is not available in server mode');
+  }
+};
+
+/**
+ * Read a text file, transcoding it to UTF-8 (local mode only)
+ * @param {string} filePath - File path to read from
+ * @param {string} [encoding] - 'utf-8' (default), 'latin1' or 'windows-1252'
+ * @returns {Promise<{content: string, encoding: string, lossy: boolean}>} Content of
+ *   the file; `lossy` is set when invalid bytes were replaced, so characters may be wrong
+ */
+export const readTextFile = async (filePath, encoding) => {
+  if (isLocalMode()) {
+    if (isTauriAvailable()) {
+      return await invokeTauri('read_text_file', { filePath, encoding });
+    }
+    throw new Error('Local mode file read not available');
+  } else {
+    throw new Error('Reading files with an encoding is not available in server mode');
+  }
+};
+
 /**
  * Read content from a file
  * @param {string} filePath - File path to read from
+ * @param {string} [encoding] - Text encoding in local mode (see readTextFile)
  * @returns {Promise<string>} Content of the file
  */
-export const readFile = async (filePath) => {
+export const readFile = async (filePath, encoding) => {
   if (isLocalMode()) {
     if (isTauriAvailable()) {
-      return await invokeTauri('read_text_file', { filePath });
+      const result = await readTextFile(filePath, encoding);
+      if (result.lossy) {
+        console.warn(`Some characters in ${filePath} could not be decoded as ${result.encoding}`);
+      }
+      return result.content;
     }
     throw new Error('Local mode file read not available');
   } else {
@@ -250,6 +382,23 @@ export const readFile = async (filePath) => {
   }
 };
 
+/**
+ * Read the first lines of a file, e.g. to preview a large CSV
+ * @param {string} filePath - File path to read from
+ * @param {number} lines - Maximum number of lines to return
+ * @returns {Promise<string>} The lines, with their original line endings
+ */
+export const readFileHead = async (filePath, lines) => {
+  if (isLocalMode()) {
+    if (isTauriAvailable()) {
+      return await invokeTauri('read_file_head', { path: filePath, lines });
+    }
+    throw new Error('Local mode file read not available');
+  } else {
+    throw new Error('File preview is not available in server mode');
+  }
+};
+
 /**
  * Open a folder in the native file browser (desktop) or copy path to clipboard (server)
  * @param {string} folderPath - Folder path to open or copy
@@ -266,21 +415,70 @@ export const openFolder = async (folderPath) => {
   }
 };
 
+/**
+ * Reveal a file or folder in Finder/Explorer (desktop) or copy its path to clipboard (server)
+ * @param {string} path - File or folder to reveal
+ */
+export const revealInFileManager = async (path) => {
+  if (isLocalMode()) {
+    if (isTauriAvailable()) {
+      await invokeTauri('reveal_in_file_manager', { path });
+      return;
+    }
+    throw new Error('Local mode reveal in file manager not available');
+  } else {
+    await navigator.clipboard.writeText(path);
+  }
+};
+
+/**
+ * Why the app's config/data folders are unusable (so settings won't be saved),
+ * or null when they are fine or outside the desktop app
+ */
+export const getStorageError = async () => {
+  if (!isTauriAvailable()) {
+    return null;
+  }
+  const dirs = await invokeTauri('get_app_dirs');
+  return dirs.error || null;
+};
+
+/**
+ * Call `callback` with the error when the desktop app reports `storage-unavailable`.
+ * Resolves to a function that stops listening.
+ */
+export const onStorageUnavailable = async (callback) => {
+  if (!isTauriAvailable()) {
+    return () => { };
+  }
+  const { listen } = await import('@tauri-apps/api/event');
+  return listen('storage-unavailable', event => callback(event.payload));
+};
+
 /**
  * Default export with all file operations
  */
 const fileOperations = {
   selectFiles,
   selectFolder,
+  selectFolders,
+  scanFolderForAudio,
   selectCSVFiles,
   selectTextFiles,
   selectJSONFiles,
   selectModelFiles,
   generateUniqueFolderName,
+  createUniqueFolder,
   saveFile,
   writeFile,
+  appendFile,
   readFile,
-  openFolder
+  readTextFile,
+  readFileHead,
+  openFolder,
+  revealInFileManager,
+  getStorageError,
+  onStorageUnavailable
 };
 
 export default fileOperations;
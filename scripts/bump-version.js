@@ -6,6 +6,7 @@
  * - frontend/package.json
  * - frontend/src-tauri/Cargo.toml
  * - frontend/src-tauri/tauri.conf.json
+ * - backend/lightweight_server.py (SERVER_VERSION)
  *
  * Usage:
  *   node scripts/bump-version.js <new-version>
@@ -39,7 +40,8 @@ const ROOT = path.resolve(__dirname, '..');
 const FILES = {
   packageJson: path.join(ROOT, 'frontend', 'package.json'),
   cargoToml: path.join(ROOT, 'frontend', 'src-tauri', 'Cargo.toml'),
-  tauriConf: path.join(ROOT, 'frontend', 'src-tauri', 'tauri.conf.json')
+  tauriConf: path.join(ROOT, 'frontend', 'src-tauri', 'tauri.conf.json'),
+  server: path.join(ROOT, 'backend', 'lightweight_server.py')
 };
 
 console.log(`\n🔧 Bumping version to: ${newVersion}\n`);
@@ -87,6 +89,23 @@ try {
   process.exit(1);
 }
 
+// Update backend/lightweight_server.py
+try {
+  const serverPath = FILES.server;
+  let server = fs.readFileSync(serverPath, 'utf8');
+  const versionMatch = server.match(/^SERVER_VERSION = "([^"]+)"/m);
+  if (!versionMatch) {
+    throw new Error('Could not find SERVER_VERSION in lightweight_server.py');
+  }
+  const oldVersion = versionMatch[1];
+  server = server.replace(/^SERVER_VERSION = "[^"]+"$/m, `SERVER_VERSION = "${newVersion}"`);
+  fs.writeFileSync(serverPath, server);
+  console.log(`✅ backend/lightweight_server.py: ${oldVersion} → ${newVersion}`);
+} catch (err) {
+  console.error(`❌ Failed to update lightweight_server.py: ${err.message}`);
+  process.exit(1);
+}
+
 console.log(`\n✨ Version updated successfully to ${newVersion}\n`);
 console.log('Next steps:');
 console.log('  1. Review changes: git diff');